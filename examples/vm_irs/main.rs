@@ -7,8 +7,8 @@
 //! * ⚠️需要用到[`narsese`]库中的「[枚举Narsese](`narsese::enum_narsese`)」特性
 
 use navm::{
-    cmd::Cmd,
-    vm::{VmLauncher, VmRuntime},
+    cmd::{Cmd, CmdReader},
+    vm::{VmLauncher, VmRuntime, VmRuntimeExt},
 };
 use std::io::stdin;
 
@@ -26,38 +26,28 @@ fn main() {
 }
 
 fn repl(mut vm: impl VmRuntime) {
-    let mut buf = String::new();
-    loop {
-        // 读取缓冲区内容
-        if let Err(e) = stdin().read_line(&mut buf) {
-            println!("读取行错误：{e}");
+    // 逐行读取并解析指令 | 空行、CRLF等由读取器处理
+    for (line_no, result) in CmdReader::new(stdin().lock()) {
+        match result {
+            Ok(cmd) => repl_cmd(&mut vm, cmd),
+            Err(e) => println!("第{line_no}行指令解析错误: {e}"),
         }
-        let line = buf.trim();
-        if line.is_empty() {
-            continue;
-        }
-        repl_line(&mut vm, line);
-
-        // 清空缓冲区
-        buf.clear();
     }
 }
 
-/// REPL执行一行
-fn repl_line(vm: &mut impl VmRuntime, line: &str) {
-    // 解析&输入
-    match Cmd::parse(line) {
-        Ok(cmd) => {
-            if let Err(e) = vm.input_cmd(cmd) {
-                println!("指令输入时发生错误：{e}");
-            }
-        }
-        Err(e) => println!("指令解析错误: {}", e),
+/// REPL执行一条指令
+fn repl_cmd(vm: &mut impl VmRuntime, cmd: Cmd) {
+    // 输入
+    if let Err(e) = vm.input_cmd(cmd) {
+        println!("指令输入时发生错误：{e}");
     }
 
     // 拉取输出
-    while let Ok(Some(output)) = vm.try_fetch_output() {
-        // 输出/JSON
-        println!("{}", output.to_json_string());
+    for output in vm.drain_outputs() {
+        match output {
+            // 输出/JSON
+            Ok(output) => println!("{}", output.to_json_string()),
+            Err(e) => println!("拉取输出时发生错误：{e}"),
+        }
     }
 }
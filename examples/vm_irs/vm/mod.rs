@@ -24,7 +24,7 @@ use navm::{
     output::Output,
     vm::{VmLauncher, VmRuntime, VmStatus},
 };
-use std::collections::VecDeque;
+use std::{collections::VecDeque, time::Duration};
 
 /// 虚拟机启动器
 pub struct VmDed;
@@ -338,6 +338,11 @@ impl VmRuntime for VmRuntimeDed {
         Ok(self.output_cache.pop_front())
     }
 
+    fn fetch_output_timeout(&mut self, _timeout: Duration) -> Result<Option<Output>> {
+        // * 🚩输出均在输入指令时同步产生：等待期间不会有新输出，无需等待
+        self.try_fetch_output()
+    }
+
     fn status(&self) -> &VmStatus {
         // 始终「运行中」
         &VmStatus::Running
@@ -0,0 +1,230 @@
+//! 提供对「NAVM指令」的快捷构造支持
+//! * 🎯让测试代码、示例代码无需书写冗长的结构体字面量
+//! * 📌构造函数与指令头同名（小写）
+//!   * ⚠️例外：[`Cmd::NEW`]对应`new_target`，以免与通用的构造函数`new`混淆
+//!
+//! ! 注意：内部导出了宏，所以不能用[`nar_dev_utils::mod_and_pub_use`]合并
+
+use super::{Cmd, ParseResult, Target};
+use narsese::lexical::Task as LexicalTask;
+
+impl Cmd {
+    /// 构造指令[`Cmd::SAV`]
+    pub fn sav(target: impl Into<Target>, path: impl Into<String>) -> Self {
+        Cmd::SAV {
+            target: target.into(),
+            path: path.into(),
+        }
+    }
+
+    /// 构造指令[`Cmd::LOA`]
+    pub fn loa(target: impl Into<Target>, path: impl Into<String>) -> Self {
+        Cmd::LOA {
+            target: target.into(),
+            path: path.into(),
+        }
+    }
+
+    /// 构造指令[`Cmd::RES`]
+    pub fn res(target: impl Into<Target>) -> Self {
+        Cmd::RES {
+            target: target.into(),
+        }
+    }
+
+    /// 构造指令[`Cmd::NSE`]
+    /// * 🚩直接使用词法任务
+    pub fn nse(task: LexicalTask) -> Self {
+        Cmd::NSE(task)
+    }
+
+    /// 从CommonNarsese文本构造指令[`Cmd::NSE`]
+    /// * 🚩与解析`NSE`指令的逻辑一致：语句将被转换为「空预算任务」
+    /// * 📌报错时的出错范围相对于传入的文本
+    pub fn nse_str(narsese: &str) -> ParseResult<Self> {
+        Self::parse_str_params("NSE", narsese)
+    }
+
+    /// 构造指令[`Cmd::NEW`]
+    /// * 📌不命名为`new`：仅对应「新建推理器」的指令，并非通用的构造函数
+    pub fn new_target(target: impl Into<String>) -> Self {
+        Cmd::NEW {
+            target: target.into(),
+        }
+    }
+
+    /// 构造指令[`Cmd::DEL`]
+    pub fn del(target: impl Into<String>) -> Self {
+        Cmd::DEL {
+            target: target.into(),
+        }
+    }
+
+    /// 构造指令[`Cmd::CYC`]
+    pub fn cyc(steps: usize) -> Self {
+        Cmd::CYC(steps)
+    }
+
+    /// 构造指令[`Cmd::VOL`]
+    pub fn vol(volume: usize) -> Self {
+        Cmd::VOL(volume)
+    }
+
+    /// 构造指令[`Cmd::REG`]
+    pub fn reg(name: impl Into<String>) -> Self {
+        Cmd::REG { name: name.into() }
+    }
+
+    /// 构造指令[`Cmd::INF`]
+    pub fn inf(source: impl Into<Target>) -> Self {
+        Cmd::INF {
+            source: source.into(),
+        }
+    }
+
+    /// 构造指令[`Cmd::HLP`]
+    pub fn hlp(name: impl Into<String>) -> Self {
+        Cmd::HLP { name: name.into() }
+    }
+
+    /// 构造指令[`Cmd::REM`]
+    pub fn rem(comment: impl Into<String>) -> Self {
+        Cmd::REM {
+            comment: comment.into(),
+        }
+    }
+
+    /// 构造指令[`Cmd::EXI`]
+    pub fn exi(reason: impl Into<String>) -> Self {
+        Cmd::EXI {
+            reason: reason.into(),
+        }
+    }
+
+    /// 构造自定义指令[`Cmd::Custom`]
+    pub fn custom(head: impl Into<String>, tail: impl Into<String>) -> Self {
+        Cmd::Custom {
+            head: head.into(),
+            tail: tail.into(),
+        }
+    }
+}
+
+/// 快捷构造宏
+/// * 🚩以「指令头 参数」的形式构造指令
+///   * 📄`cmd!(CYC 10)` `cmd!(SAV "memory", "a.json")` `cmd!(NSE "<A --> B>.")`
+/// * 📌可缺省的字符串参数（与「松弛解析」一致）可省略，此时取空字串
+/// * 📌表外的指令头⇒自定义指令：`cmd!(HEAD "tail")`
+/// * 📌`NSE`的Narsese须为字符串字面量
+///   * ⚠️不支持内联Narsese：词法标记的间距不可控，且无法表示`:\:`等时间戳
+/// * ⚠️`NSE`的Narsese在运行时解析，若解析失败将发生panic
+#[macro_export]
+macro_rules! cmd {
+    // * 🚩Narsese：字符串字面量
+    (NSE $narsese:literal) => {
+        $crate::cmd::Cmd::nse_str($narsese).expect("NSE指令的Narsese解析失败")
+    };
+    // * 🚩数值
+    (CYC $steps:expr) => {
+        $crate::cmd::Cmd::CYC($steps)
+    };
+    (VOL $volume:expr) => {
+        $crate::cmd::Cmd::VOL($volume)
+    };
+    // * 🚩目标+路径
+    (SAV $($target:expr $(, $path:expr)?)?) => {
+        $crate::cmd::Cmd::SAV {
+            target: $crate::cmd!(@TARGET $($target)?),
+            path: $crate::cmd!(@STR $($($path)?)?),
+        }
+    };
+    (LOA $($target:expr $(, $path:expr)?)?) => {
+        $crate::cmd::Cmd::LOA {
+            target: $crate::cmd!(@TARGET $($target)?),
+            path: $crate::cmd!(@STR $($($path)?)?),
+        }
+    };
+    // * 🚩单个字符串参数
+    (RES $($target:expr)?) => {
+        $crate::cmd::Cmd::RES { target: $crate::cmd!(@TARGET $($target)?) }
+    };
+    (NEW $target:expr) => {
+        $crate::cmd::Cmd::NEW { target: $crate::cmd!(@STR $target) }
+    };
+    (DEL $target:expr) => {
+        $crate::cmd::Cmd::DEL { target: $crate::cmd!(@STR $target) }
+    };
+    (REG $name:expr) => {
+        $crate::cmd::Cmd::REG { name: $crate::cmd!(@STR $name) }
+    };
+    (INF $($source:expr)?) => {
+        $crate::cmd::Cmd::INF { source: $crate::cmd!(@TARGET $($source)?) }
+    };
+    (HLP $($name:expr)?) => {
+        $crate::cmd::Cmd::HLP { name: $crate::cmd!(@STR $($name)?) }
+    };
+    (REM $($comment:expr)?) => {
+        $crate::cmd::Cmd::REM { comment: $crate::cmd!(@STR $($comment)?) }
+    };
+    (EXI $($reason:expr)?) => {
+        $crate::cmd::Cmd::EXI { reason: $crate::cmd!(@STR $($reason)?) }
+    };
+    // * 🚩字符串参数：缺省⇒空字串
+    (@STR) => {
+        ::std::string::String::new()
+    };
+    (@STR $s:expr) => {
+        ::std::string::String::from($s)
+    };
+    // * 🚩目标参数：与文本解析一致，已知名称⇒对应变体
+    (@TARGET $($s:expr)?) => {
+        $crate::cmd::Target::from($crate::cmd!(@STR $($s)?))
+    };
+    // * 🚩表外的指令头⇒自定义指令
+    ($head:ident $($tail:expr)?) => {
+        $crate::cmd::Cmd::Custom {
+            head: stringify!($head).into(),
+            tail: $crate::cmd!(@STR $($tail)?),
+        }
+    };
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nar_dev_utils::asserts;
+
+    /// 测试/构造函数与宏
+    /// * 🎯与解析结果一致
+    #[test]
+    fn test_construct() {
+        let parse = |line: &str| Cmd::parse(line).unwrap();
+        asserts! {
+            Cmd::sav("memory", "a.json") => parse("SAV memory a.json")
+            Cmd::cyc(10) => parse("CYC 10")
+            Cmd::nse_str("<A --> B>.").unwrap() => parse("NSE <A --> B>.")
+            Cmd::custom("HEAD", "tail") => parse("HEAD tail")
+            Cmd::new_target("reasoner") => parse("NEW reasoner")
+            cmd!(NSE "<A --> B>. :|: %1.0;0.9%") => parse("NSE <A --> B>. :|: %1.0;0.9%")
+            cmd!(NSE "$0.5;0.5;0.5$ <(&&, A, B) --> C>!") => parse("NSE $0.5;0.5;0.5$ <(&&, A, B) --> C>!")
+            cmd!(NSE "<A --> B>. :\\:") => parse("NSE <A --> B>. :\\:")
+            cmd!(NSE "<A --> B>?") => parse("NSE <A --> B>?")
+            cmd!(CYC 10) => Cmd::cyc(10)
+            cmd!(VOL 0) => Cmd::vol(0)
+            cmd!(SAV "memory", "a.json") => Cmd::sav("memory", "a.json")
+            cmd!(LOA "memory") => Cmd::loa("memory", "")
+            cmd!(RES) => Cmd::res("")
+            cmd!(NEW "reasoner") => Cmd::new_target("reasoner")
+            cmd!(DEL "reasoner") => Cmd::del("reasoner")
+            cmd!(REG "left") => Cmd::reg("left")
+            cmd!(INF "memory") => Cmd::inf("memory")
+            cmd!(HLP) => Cmd::hlp("")
+            cmd!(REM "comment") => Cmd::rem("comment")
+            cmd!(EXI "reason") => Cmd::exi("reason")
+            cmd!(HEAD "tail") => Cmd::custom("HEAD", "tail")
+        }
+        // Narsese解析失败
+        assert!(Cmd::nse_str("<A --> B").is_err());
+    }
+}
@@ -0,0 +1,409 @@
+//! 负责「NAVM指令」与其它类型的转换
+//! * 🎯与「NAVM输出」的[`crate::output::OutputJSON`]对称
+//! * 📌主要是JSON等外部可交换的格式
+//! * 🚩约定：其中所有Narsese均遵循CommonNarsese格式规范
+//!
+//! # Reference
+//!
+//! 📄JSON格式参考如下TypeScript定义：
+//! ```typescript
+//! export type NAVMCmd = {
+//!     /** 指令头（全大写） */
+//!     head: string
+//!     /** SAV/LOA/RES/NEW/DEL：目标 */
+//!     target?: string
+//!     /** SAV/LOA：路径 */
+//!     path?: string
+//!     /** NSE：ASCII CommonNarsese字符串 */
+//!     narsese?: string
+//!     /** CYC/VOL：数值 */
+//!     num?: number
+//!     /** REG/HLP：名称 */
+//!     name?: string
+//!     /** INF：来源 */
+//!     source?: string
+//!     /** REM：注释 */
+//!     comment?: string
+//!     /** EXI：原因 */
+//!     reason?: string
+//!     /** 自定义指令：指令尾 */
+//!     tail?: string
+//! }
+//! ```
+//!
+//! 另请参考其所对接的结构[`CmdJSON`]
+//!
+//! ⚠️扩展指令[`Cmd::Ext`]序列化后与自定义指令同形（只有`head`与`tail`）
+//! * 📌直接反序列化（含[`Deserialize`]）只能得到[`Cmd::Custom`]
+//! * 🚩需还原扩展指令时，使用[`Cmd::try_from_json_struct_with`]并传入注册表
+
+use super::{Cmd, CmdRegistry, NseFormat};
+use anyhow::{anyhow, Result};
+use narsese::conversion::string::impl_lexical::format_instances::FORMAT_ASCII;
+use serde::{Deserialize, Serialize};
+
+/// 用于统一存储「JSON化的NAVM指令」的结构
+/// * 🎯对包含各种不同字段的枚举[`Cmd`]进行信息压缩
+///   * 🚩指令头+各指令的专有字段（可选）
+/// * 📌字段名与[`Cmd`]各变体的字段名一致
+/// * 📌反序列化时，缺省的字符串字段视作空字串（与「松弛解析」一致）
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CmdJSON {
+    /// 指令头
+    pub head: String,
+
+    /// 专有：目标
+    /// * 📄`SAV` `LOA` `RES` `NEW` `DEL`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub target: Option<String>,
+
+    /// 专有：路径
+    /// * 📄`SAV` `LOA`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub path: Option<String>,
+
+    /// 专有：Narsese
+    /// * 📄`NSE`
+    /// * 📌格式：ASCII CommonNarsese
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub narsese: Option<String>,
+
+    /// 专有：数值
+    /// * 📄`CYC` `VOL`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub num: Option<usize>,
+
+    /// 专有：名称
+    /// * 📄`REG` `HLP`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// 专有：来源
+    /// * 📄`INF`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub source: Option<String>,
+
+    /// 专有：注释
+    /// * 📄`REM`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub comment: Option<String>,
+
+    /// 专有：原因
+    /// * 📄`EXI`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub reason: Option<String>,
+
+    /// 专有：指令尾
+    /// * 📄自定义指令
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub tail: Option<String>,
+}
+
+/// 将「JSON化的NAVM指令」转换为字符串
+/// * 🔗参考[`crate::output::OutputJSON`]的实现
+#[cfg(feature = "serde_json")]
+impl std::fmt::Display for CmdJSON {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let string = serde_json::to_string(self).map_err(|_| std::fmt::Error)?;
+        f.write_str(&string)
+    }
+}
+
+impl Cmd {
+    // * 序列化 * //
+
+    /// 将NAVM指令转换为JSON结构
+    pub fn to_json_struct(&self) -> CmdJSON {
+        // 共有：指令头
+        let head = self.head().to_owned();
+        // 专有：各自的字段
+        let mut json = CmdJSON {
+            head,
+            ..Default::default()
+        };
+        match self {
+            Cmd::SAV { target, path } | Cmd::LOA { target, path } => {
+                json.target = Some(target.to_string());
+                json.path = Some(path.clone());
+            }
+            Cmd::RES { target } => json.target = Some(target.to_string()),
+            Cmd::NEW { target } | Cmd::DEL { target } => json.target = Some(target.clone()),
+            // * 🚩将词法Narsese转换为ASCII CommonNarsese字符串
+            Cmd::NSE(task) => json.narsese = Some(NseFormat::Ascii.format_task(task)),
+            Cmd::CYC(num) | Cmd::VOL(num) => json.num = Some(*num),
+            Cmd::REG { name } | Cmd::HLP { name } => json.name = Some(name.clone()),
+            Cmd::INF { source } => json.source = Some(source.to_string()),
+            Cmd::REM { comment } => json.comment = Some(comment.clone()),
+            Cmd::EXI { reason } => json.reason = Some(reason.clone()),
+            Cmd::Custom { tail, .. } => json.tail = Some(tail.clone()),
+            // * 🚩扩展指令：与自定义指令同形
+            //   * ⚠️不经注册表反序列化时，将还原为自定义指令
+            Cmd::Ext(ext) => json.tail = Some(ext.tail()),
+        }
+        json
+    }
+
+    /// 将NAVM指令转换为JSON字符串
+    /// * 🚩先转换为JSON结构，再将其转换为字符串
+    #[cfg(feature = "serde_json")]
+    pub fn to_json_string(&self) -> String {
+        self.to_json_struct().to_string()
+    }
+
+    /// 将NAVM指令数组转换为JSON数组
+    #[cfg(feature = "serde_json")]
+    pub fn vec_to_json_string(v: &[Self]) -> String {
+        serde_json::to_string(v).expect("不会转换失败：内部JSON结构总是转换成功")
+    }
+
+    // * 反序列化 * //
+
+    /// 尝试从中间「JSON结构体」折叠为自身
+    /// * 🚩根据「指令头」取用对应字段
+    /// * ⚠️扩展指令将被还原为[`Cmd::Custom`]：有损
+    ///   * 🔗需还原扩展指令时，使用[`Cmd::try_from_json_struct_with`]
+    pub fn try_from_json_struct(json: CmdJSON) -> Result<Self> {
+        Self::try_from_json_struct_with(json, &CmdRegistry::default())
+    }
+
+    /// 尝试从中间「JSON结构体」折叠为自身，并识别其中注册过的扩展指令
+    /// * 🚩指令头在注册表中⇒以`tail`字段交由注册的解析器，产生[`Cmd::Ext`]
+    ///   * ⚠️注册表优先于内置指令（与[`Cmd::parse_with`]一致）
+    /// * 🚩否则⇒与[`Cmd::try_from_json_struct`]一致
+    pub fn try_from_json_struct_with(json: CmdJSON, registry: &CmdRegistry) -> Result<Self> {
+        if let Some(result) = registry.parse_ext(&json.head, json.tail.as_deref().unwrap_or("")) {
+            return Ok(Cmd::Ext(result?));
+        }
+        let CmdJSON {
+            head,
+            target,
+            path,
+            narsese,
+            num,
+            name,
+            source,
+            comment,
+            reason,
+            tail,
+        } = json;
+        // 字符串字段：缺省为空
+        let target = target.unwrap_or_default();
+        let path = path.unwrap_or_default();
+        let name = name.unwrap_or_default();
+        // 数值字段：必须存在
+        let num = || num.ok_or(anyhow!("指令「{head}」缺少数值字段`num`"));
+        // 与文本解析一致：指令头不区分大小写
+        let cmd = match head.to_uppercase().as_str() {
+            "SAV" => Cmd::SAV {
+                target: target.into(),
+                path,
+            },
+            "LOA" => Cmd::LOA {
+                target: target.into(),
+                path,
+            },
+            "RES" => Cmd::RES {
+                target: target.into(),
+            },
+            "NSE" => {
+                let narsese = narsese.ok_or(anyhow!("指令「{head}」缺少Narsese字段`narsese`"))?;
+                // ! 语句将被转换为「空预算任务」
+                Cmd::NSE(FORMAT_ASCII.parse(&narsese)?.try_into_task_compatible()?)
+            }
+            "NEW" => Cmd::NEW { target },
+            "DEL" => Cmd::DEL { target },
+            "CYC" => Cmd::CYC(num()?),
+            "VOL" => Cmd::VOL(num()?),
+            "REG" => Cmd::REG { name },
+            "INF" => Cmd::INF {
+                source: source.unwrap_or_default().into(),
+            },
+            "HLP" => Cmd::HLP { name },
+            "REM" => Cmd::REM {
+                comment: comment.unwrap_or_default(),
+            },
+            "EXI" => Cmd::EXI {
+                reason: reason.unwrap_or_default(),
+            },
+            other => Cmd::Custom {
+                head: other.into(),
+                tail: tail.unwrap_or_default(),
+            },
+        };
+        Ok(cmd)
+    }
+
+    /// 尝试从JSON字符串中解析出指令
+    /// * 🚩先解析出中间JSON结构体，再将其折叠为指令
+    #[cfg(feature = "serde_json")]
+    pub fn try_from_json_string(s: &str) -> Result<Self> {
+        Self::try_from_json_struct(CmdJSON::try_from_json_string(s)?)
+    }
+
+    /// 尝试从JSON字符串中解析出指令，并识别其中注册过的扩展指令
+    /// * 🔗[`Cmd::try_from_json_struct_with`]
+    #[cfg(feature = "serde_json")]
+    pub fn try_from_json_string_with(s: &str, registry: &CmdRegistry) -> Result<Self> {
+        Self::try_from_json_struct_with(CmdJSON::try_from_json_string(s)?, registry)
+    }
+
+    /// 将JSON字符串转换为「指令数组」
+    #[cfg(feature = "serde_json")]
+    pub fn vec_try_from_json_string(s: &str) -> Result<Vec<Self>> {
+        Ok(serde_json::from_str(s)?)
+    }
+}
+
+/// 对指令直接实现序列化
+impl Serialize for Cmd {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        // 先转换为JSON对象，再以JSON对象进行序列化
+        self.to_json_struct().serialize(serializer)
+    }
+}
+
+/// 对指令直接实现反序列化
+/// * ⚠️无注册表：扩展指令将被还原为[`Cmd::Custom`]
+impl<'de> Deserialize<'de> for Cmd {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+        // 先反序列化成JSON对象，再从JSON对象解析，并转换其中的错误类型
+        let json = CmdJSON::deserialize(deserializer)?;
+        Self::try_from_json_struct(json).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl TryFrom<&str> for CmdJSON {
+    type Error = anyhow::Error;
+
+    fn try_from(s: &str) -> Result<Self> {
+        CmdJSON::try_from_json_string(s)
+    }
+}
+
+impl TryFrom<CmdJSON> for Cmd {
+    type Error = anyhow::Error;
+
+    fn try_from(json: CmdJSON) -> Result<Self> {
+        Cmd::try_from_json_struct(json)
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl CmdJSON {
+    /// 尝试从JSON字符串中解析出中间「JSON结构体」
+    /// * 🚩仅解析JSON结构，不校验「指令头」与字段是否相符
+    ///   * 🔗需得到指令时，使用[`Cmd::try_from_json_string`]
+    /// * 🚩JSON语法错误、非对象、缺少`head`、字段类型不符⇒报错
+    ///   * 📌未知字段将被忽略
+    pub fn try_from_json_string(s: &str) -> Result<Self> {
+        Ok(serde_json::from_str(s)?)
+    }
+}
+
+impl From<Cmd> for CmdJSON {
+    fn from(cmd: Cmd) -> Self {
+        cmd.to_json_struct()
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmd::{extension::tests::Repeat, structs::tests::samples};
+
+    /// 测试/与JSON结构互转
+    /// * 🎯能与JSON结构无损互转
+    #[test]
+    fn test_json_struct() {
+        for cmd in samples() {
+            let json = cmd.to_json_struct();
+            println!("{json:?}");
+            let re_converted = Cmd::try_from_json_struct(json).expect("JSON结构解析失败");
+            assert_eq!(cmd, re_converted);
+        }
+    }
+
+    /// 测试/与JSON字串互转
+    /// * 🎯能与JSON字符串无损互转
+    #[test]
+    #[cfg(feature = "serde_json")]
+    fn test_json_str() {
+        let samples = samples();
+        for cmd in &samples {
+            let json_str = cmd.to_json_string();
+            println!("{json_str}");
+            let re_converted = Cmd::try_from_json_string(&json_str).expect("JSON字串解析失败");
+            assert_eq!(*cmd, re_converted);
+        }
+        // 样本集总体的测试
+        let json_str = Cmd::vec_to_json_string(&samples);
+        let re_converted = Cmd::vec_try_from_json_string(&json_str).expect("JSON转换失败");
+        assert_eq!(samples, re_converted);
+        // 缺省字段
+        assert_eq!(
+            Cmd::try_from_json_string(r#"{"head":"nse","narsese":"<A --> B>."}"#).unwrap(),
+            Cmd::parse("NSE <A --> B>.").unwrap()
+        );
+        assert!(Cmd::try_from_json_string(r#"{"head":"CYC"}"#).is_err());
+    }
+
+    /// 测试/扩展指令
+    /// * 🎯不经注册表⇒有损（还原为自定义指令）；经注册表⇒无损
+    #[test]
+    fn test_json_ext() {
+        let mut registry = CmdRegistry::new();
+        registry.register::<Repeat>();
+        let cmd = registry.parse("REP 3").unwrap();
+        let json = cmd.to_json_struct();
+        assert_eq!(
+            json,
+            CmdJSON {
+                head: "REP".into(),
+                tail: Some("3".into()),
+                ..Default::default()
+            }
+        );
+        // 不经注册表
+        assert_eq!(
+            Cmd::try_from_json_struct(json.clone()).unwrap(),
+            Cmd::Custom {
+                head: "REP".into(),
+                tail: "3".into(),
+            }
+        );
+        // 经注册表
+        let re_converted = Cmd::try_from_json_struct_with(json, &registry).unwrap();
+        assert_eq!(re_converted, cmd);
+        assert!(matches!(&re_converted, Cmd::Ext(ext) if ext.downcast_ref::<Repeat>().is_some()));
+        // 指令尾解析失败⇒报错
+        let json = CmdJSON {
+            head: "rep".into(),
+            tail: Some("x".into()),
+            ..Default::default()
+        };
+        assert!(Cmd::try_from_json_struct_with(json, &registry).is_err());
+        // 未注册的指令头不受影响
+        let json = Cmd::CYC(3).to_json_struct();
+        assert_eq!(Cmd::try_from_json_struct_with(json, &registry).unwrap(), Cmd::CYC(3));
+    }
+}
@@ -0,0 +1,233 @@
+//! 提供「扩展指令」的注册与解析支持
+//! * 🎯让各CIN特有的指令（如ONA的`*motorbabbling`）成为强类型的一等指令
+//!   * 📌而非落入[`Cmd::Custom`]后由各运行时自行重解析「指令尾」
+//! * 🚩由使用者向[`CmdRegistry`]注册「指令头+解析器」
+//!   * 📌解析：[`Cmd::parse_with`]
+//!   * 📌格式化：与其它指令一致，为「指令头 指令尾」
+
+use super::{Cmd, ParseResult};
+use std::{
+    any::Any,
+    collections::HashMap,
+    fmt::Debug,
+    hash::{Hash, Hasher},
+};
+
+/// 扩展指令（特征对象）
+/// * 🎯作为[`ExtCmd`]的内容，统一「指令头+指令尾」接口
+/// * 📌通常无需手动实现：实现[`TypedCmd`]即可
+pub trait CustomCmd: Debug + Send + Sync + Any {
+    /// 指令头
+    fn head(&self) -> &str;
+
+    /// 指令尾
+    /// * 🚩须能被注册的解析器重新解析
+    fn tail(&self) -> String;
+
+    /// 复制到新的特征对象
+    fn box_clone(&self) -> Box<dyn CustomCmd>;
+
+    /// 转换为[`Any`]，以便向下转型
+    fn as_any(&self) -> &dyn Any;
+}
+
+/// 强类型的扩展指令
+/// * 🎯以「固定指令头+解析/格式化指令尾」的方式定义扩展指令
+/// * 📌注册：[`CmdRegistry::register`]
+pub trait TypedCmd: Debug + Clone + Send + Sync + 'static {
+    /// 指令头
+    const HEAD: &'static str;
+
+    /// 从指令尾解析
+    /// * 📌报错时的出错范围相对于指令尾
+    fn parse_tail(tail: &str) -> ParseResult<Self>;
+
+    /// 格式化为指令尾
+    fn format_tail(&self) -> String;
+}
+
+impl<T: TypedCmd> CustomCmd for T {
+    fn head(&self) -> &str {
+        T::HEAD
+    }
+
+    fn tail(&self) -> String {
+        self.format_tail()
+    }
+
+    fn box_clone(&self) -> Box<dyn CustomCmd> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// 扩展指令
+/// * 🚩对特征对象[`CustomCmd`]的封装
+///   * 📌实现[`Clone`] [`PartialEq`] [`Hash`]等，以便嵌入[`Cmd`]
+///   * 📌比对、散列均基于「指令头+指令尾」
+pub struct ExtCmd(Box<dyn CustomCmd>);
+
+impl ExtCmd {
+    /// 构造函数
+    pub fn new(cmd: impl CustomCmd) -> Self {
+        Self(Box::new(cmd))
+    }
+
+    /// 指令头
+    pub fn head(&self) -> &str {
+        self.0.head()
+    }
+
+    /// 指令尾
+    pub fn tail(&self) -> String {
+        self.0.tail()
+    }
+
+    /// 尝试向下转型为具体的扩展指令
+    pub fn downcast_ref<T: CustomCmd>(&self) -> Option<&T> {
+        self.0.as_any().downcast_ref()
+    }
+}
+
+impl Debug for ExtCmd {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Clone for ExtCmd {
+    fn clone(&self) -> Self {
+        Self(self.0.box_clone())
+    }
+}
+
+impl PartialEq for ExtCmd {
+    fn eq(&self, other: &Self) -> bool {
+        self.head() == other.head() && self.tail() == other.tail()
+    }
+}
+
+impl Eq for ExtCmd {}
+
+impl Hash for ExtCmd {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.head().hash(state);
+        self.tail().hash(state);
+    }
+}
+
+impl<T: CustomCmd> From<T> for ExtCmd {
+    fn from(cmd: T) -> Self {
+        Self::new(cmd)
+    }
+}
+
+/// 扩展指令的解析器
+/// * 🚩传入指令尾，返回扩展指令
+/// * 📌报错时的出错范围相对于指令尾
+pub type ExtCmdParser = fn(&str) -> ParseResult<ExtCmd>;
+
+/// 扩展指令注册表
+/// * 🎯存储「指令头→解析器」的映射
+/// * 📌指令头不区分大小写（与内置指令一致）
+#[derive(Debug, Clone, Default)]
+pub struct CmdRegistry {
+    /// 解析器映射表
+    /// * 🚩键为大写的指令头
+    parsers: HashMap<String, ExtCmdParser>,
+}
+
+impl CmdRegistry {
+    /// 构造函数
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册强类型的扩展指令
+    /// * 🚩同名指令头⇒覆盖
+    pub fn register<T: TypedCmd>(&mut self) -> &mut Self {
+        /// 解析并封装为扩展指令
+        fn parse_as<T: TypedCmd>(tail: &str) -> ParseResult<ExtCmd> {
+            T::parse_tail(tail).map(ExtCmd::new)
+        }
+        self.register_parser(T::HEAD, parse_as::<T>)
+    }
+
+    /// 以「指令头+解析器」的形式注册
+    /// * 🎯用于直接实现[`CustomCmd`]的扩展指令
+    /// * 🚩同名指令头⇒覆盖
+    pub fn register_parser(&mut self, head: &str, parser: ExtCmdParser) -> &mut Self {
+        self.parsers.insert(head.to_uppercase(), parser);
+        self
+    }
+
+    /// 指令头是否已注册
+    pub fn contains(&self, head: &str) -> bool {
+        self.parsers.contains_key(&head.to_uppercase())
+    }
+
+    /// 尝试解析扩展指令
+    /// * 🚩未注册⇒[`None`]
+    pub fn parse_ext(&self, head: &str, tail: &str) -> Option<ParseResult<ExtCmd>> {
+        self.parsers
+            .get(&head.to_uppercase())
+            .map(|parser| parser(tail))
+    }
+
+    /// 从字符串构造NAVM指令
+    /// * 🔗[`Cmd::parse_with`]的快捷方式
+    pub fn parse(&self, line: &str) -> ParseResult<Cmd> {
+        Cmd::parse_with(line, self)
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::cmd::{get_cmd_param_usize, ParseErrorKind};
+    use nar_dev_utils::asserts;
+
+    /// 测试用扩展指令
+    #[derive(Debug, Clone, PartialEq)]
+    pub(crate) struct Repeat(pub usize);
+
+    impl TypedCmd for Repeat {
+        const HEAD: &'static str = "REP";
+
+        fn parse_tail(tail: &str) -> ParseResult<Self> {
+            get_cmd_param_usize(tail).map(Repeat)
+        }
+
+        fn format_tail(&self) -> String {
+            self.0.to_string()
+        }
+    }
+
+    #[test]
+    fn test_extension() {
+        let mut registry = CmdRegistry::new();
+        registry.register::<Repeat>();
+        let cmd = registry.parse("rep 3").unwrap();
+        let Cmd::Ext(ext) = &cmd else {
+            panic!("未解析为扩展指令：{cmd:?}");
+        };
+        asserts! {
+            ext.downcast_ref::<Repeat>() => Some(&Repeat(3))
+            cmd.to_string() => "REP 3"
+            // 格式化→解析 无损
+            registry.parse(&cmd.to_string()).unwrap() => cmd
+            // 未经注册表⇒自定义指令
+            Cmd::parse("REP 3").unwrap() => Cmd::Custom { head: "REP".into(), tail: "3".into() }
+            // 未注册的指令头不受影响
+            registry.parse("CYC 3").unwrap() => Cmd::CYC(3)
+        }
+        // 错误范围转换到整行中
+        let e = registry.parse("REP x").unwrap_err();
+        assert!(matches!(e.kind(), ParseErrorKind::InvalidNumber(..)));
+        assert_eq!(e.span(), 4..5);
+    }
+}
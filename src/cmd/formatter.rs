@@ -3,11 +3,14 @@
 //!   * 📄指令[`Cmd::NSE`]只使用**CommonNarsese**语法
 //!
 
-use super::Cmd;
-use narsese::{
-    api::GetBudget, conversion::string::impl_lexical::format_instances::FORMAT_ASCII, lexical::Task,
-};
-use std::fmt::Display;
+use super::{Cmd, NseFormat, PARAM_ESCAPE, PARAM_QUOTE};
+use std::{borrow::Cow, fmt::Display};
+
+/// 所有内置指令的指令头
+/// * 📌不含[`Cmd::Custom`]与[`Cmd::Ext`]：其指令头由使用者决定
+pub const BUILTIN_HEADS: [&str; 13] = [
+    "SAV", "LOA", "RES", "NSE", "NEW", "DEL", "CYC", "VOL", "REG", "INF", "HLP", "REM", "EXI",
+];
 
 impl Cmd {
     /// 获取指令头
@@ -28,6 +31,7 @@ impl Cmd {
             Cmd::REM { .. } => "REM",
             Cmd::EXI { .. } => "EXI",
             Cmd::Custom { head, .. } => head,
+            Cmd::Ext(ext) => ext.head(),
         }
     }
 
@@ -35,13 +39,23 @@ impl Cmd {
     /// * 🚩直接匹配并返回动态字串[`String`]
     /// * 🎯便于后续重复利用
     pub fn tail(&self) -> String {
+        self.tail_with(&FormatOptions::default())
+    }
+
+    /// 按格式化选项获取指令尾
+    /// * 🎯以ASCII以外的格式展示[`Cmd::NSE`]中的Narsese
+    pub fn tail_with(&self, options: &FormatOptions) -> String {
         match self {
             // 目标+路径
-            Cmd::SAV { target, path } | Cmd::LOA { target, path } => format!("{} {}", target, path),
+            // * 🚩【2026-10-17 03:59:48】字符串参数按需加引号，保证「格式化→解析」无损
+            Cmd::SAV { target, path } | Cmd::LOA { target, path } => {
+                format_params_loose(&[target.as_str(), path])
+            }
             // 目标
-            Cmd::RES { target } | Cmd::NEW { target } | Cmd::DEL { target } => target.clone(),
+            Cmd::RES { target } => format_params_loose(&[target.as_str()]),
+            Cmd::NEW { target } | Cmd::DEL { target } => quote_param(target).into_owned(),
             // 来源
-            Cmd::INF { source } => source.clone(),
+            Cmd::INF { source } => format_params_loose(&[source.as_str()]),
             // 词法Narsese
             // * 🚩【2024-03-24 03:36:40】目前将尝试先「将『空预算任务』隐式转换为语句」然后再进行格式化
             //   * 📌避免「空预算任务」`A.`变为`$$ A.`导致的「非法输入」情况
@@ -49,11 +63,13 @@ impl Cmd {
             // * ⚡【2024-03-24 13:59:11】性能问题「隐式转换不可避免的拷贝」已初步解决
             //   * ✅使用「空预算识别+内部字段`sentence`提取」实现「引用传参」而无需转换语句
             //   * 📌目前暂不考虑将其内置进Narsese.rs
-            Cmd::NSE(narsese) => format_try_as_sentence(narsese),
+            // * 🚩【2026-10-17 04:13:42】可选格式
+            Cmd::NSE(narsese) => options.nse_format.format_task(narsese),
             // 数值
             Cmd::CYC(n) | Cmd::VOL(n) => n.to_string(),
             // 名称
-            Cmd::REG { name } | Cmd::HLP { name } => name.clone(),
+            Cmd::REG { name } => quote_param(name).into_owned(),
+            Cmd::HLP { name } => format_params_loose(&[name]),
             // 注释
             Cmd::REM { comment } => comment.clone(),
             // 原因
@@ -62,33 +78,110 @@ impl Cmd {
             Cmd::Custom {
                 tail: args_line, ..
             } => args_line.clone(),
+            Cmd::Ext(ext) => ext.tail(),
         }
     }
 }
 
-fn format_try_as_sentence(task: &Task) -> String {
-    // 手动检查预算值是否为空
-    match task.get_budget().is_empty() {
-        // 空⇒拿出内部对语句的引用，只格式化语句部分
-        true => FORMAT_ASCII.format(task.get_sentence()),
-        // 非空⇒直接格式化自身（只需引用）
-        false => FORMAT_ASCII.format(task),
+/// 按需为字符串参数加引号
+/// * 🚩仅在「直接写出会被解析成别的样子」时加引号
+///   * 📌空字串、含空白符、以引号开头
+/// * 🚩引号内转义`\\` `"` 与换行、制表等字符
+pub(crate) fn quote_param(param: &str) -> Cow<'_, str> {
+    let need_quote = param.is_empty()
+        || param.starts_with(PARAM_QUOTE)
+        || param.contains(char::is_whitespace);
+    if !need_quote {
+        return Cow::Borrowed(param);
+    }
+    let mut quoted = String::with_capacity(param.len() + 2);
+    quoted.push(PARAM_QUOTE);
+    for c in param.chars() {
+        match c {
+            PARAM_QUOTE | PARAM_ESCAPE => quoted.extend([PARAM_ESCAPE, c]),
+            '\n' => quoted.extend([PARAM_ESCAPE, 'n']),
+            '\r' => quoted.extend([PARAM_ESCAPE, 'r']),
+            '\t' => quoted.extend([PARAM_ESCAPE, 't']),
+            c => quoted.push(c),
+        }
     }
+    quoted.push(PARAM_QUOTE);
+    Cow::Owned(quoted)
 }
 
-impl From<&Cmd> for String {
-    fn from(cmd: &Cmd) -> Self {
-        // 新字串
-        let mut s = Self::new();
+/// 格式化「可缺省」的字符串参数
+/// * 🎯对应「松弛解析」：末尾的空参数直接省略，其余按需加引号
+fn format_params_loose(params: &[&str]) -> String {
+    let n_params = params.len() - params.iter().rev().take_while(|p| p.is_empty()).count();
+    params[..n_params]
+        .iter()
+        .map(|param| quote_param(param))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 格式化选项
+/// * 🎯在「默认格式化」之外，定制Narsese格式、空白等
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FormatOptions {
+    /// 指令[`Cmd::NSE`]所用的Narsese格式
+    /// * 📌[`NseFormat::Auto`]等同于ASCII
+    pub nse_format: NseFormat,
+    /// 指令尾为空时，是否仍在指令头后保留空格
+    /// * 📄`EXI `（保留）与`EXI`（不保留）
+    /// * 📌默认保留，与既有格式一致
+    pub trailing_space: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            nse_format: NseFormat::Ascii,
+            trailing_space: true,
+        }
+    }
+}
+
+impl Cmd {
+    /// 按格式化选项格式化指令
+    pub fn format_with(&self, options: &FormatOptions) -> String {
         // 通用：指令头
-        s.push_str(cmd.head());
-        // 空格分隔
-        s.push(' ');
+        let mut s = self.head().to_owned();
         // 专用：指令尾
-        s.push_str(&cmd.tail());
-        // 返回
+        let tail = self.tail_with(options);
+        // 空格分隔
+        if options.trailing_space || !tail.is_empty() {
+            s.push(' ');
+        }
+        s.push_str(&tail);
         s
     }
+
+    /// 按格式化选项展示指令
+    /// * 🎯用于[`format!`]等需要[`Display`]的场合
+    pub fn display_with(&self, options: FormatOptions) -> CmdDisplay<'_> {
+        CmdDisplay { cmd: self, options }
+    }
+}
+
+/// 按格式化选项展示的指令
+/// * 🔗[`Cmd::display_with`]
+#[derive(Debug, Clone, Copy)]
+pub struct CmdDisplay<'a> {
+    cmd: &'a Cmd,
+    options: FormatOptions,
+}
+
+impl Display for CmdDisplay<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.cmd.format_with(&self.options))
+    }
+}
+
+impl From<&Cmd> for String {
+    fn from(cmd: &Cmd) -> Self {
+        cmd.format_with(&FormatOptions::default())
+    }
 }
 
 impl Display for Cmd {
@@ -112,4 +205,41 @@ mod tests {
             println!("{cmd}");
         }
     }
+
+    /// 测试/格式化选项
+    /// * 🎯Narsese格式、尾随空格；重解析无损
+    #[test]
+    fn test_format_options() {
+        use super::super::{Cmd, NseFormat, ParseOptions};
+        use super::FormatOptions;
+        use nar_dev_utils::asserts;
+        let cmd = Cmd::parse("NSE <A --> B>. :|: %1.0;0.9%").unwrap();
+        let han = FormatOptions {
+            nse_format: NseFormat::Han,
+            ..Default::default()
+        };
+        let no_space = FormatOptions {
+            trailing_space: false,
+            ..Default::default()
+        };
+        let exi = Cmd::parse("EXI").unwrap();
+        asserts! {
+            exi.to_string() => "EXI "
+            exi.format_with(&no_space) => "EXI"
+            Cmd::parse(&exi.format_with(&no_space)).unwrap() => exi
+            Cmd::CYC(1).format_with(&no_space) => "CYC 1"
+        }
+        // 以漢文展示，再以漢文/自动识别解析回来
+        let han_str = cmd.display_with(han).to_string();
+        println!("{han_str}");
+        for nse_format in [NseFormat::Han, NseFormat::Auto] {
+            let options = ParseOptions {
+                nse_format,
+                ..Default::default()
+            };
+            assert_eq!(Cmd::parse_with_options(&han_str, &options).unwrap(), cmd);
+        }
+        // 默认解析不认漢文
+        assert!(Cmd::parse(&han_str).is_err());
+    }
 }
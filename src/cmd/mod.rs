@@ -3,10 +3,36 @@
 nar_dev_utils::mod_and_pub_use! {
     // 结构
     structs
+    // 目标
+    target
+    // 扩展
+    extension
+    // Narsese格式
+    nse_format
     // 格式化
     formatter
     // 解析
     parser
+    // 脚本
+    script
+    // 流式读写
+    stream
+    // 预处理
+    preprocess
+}
+
+// 快捷构造
+// * ⚠️内部导出了宏，所以不能用[`nar_dev_utils::mod_and_pub_use`]合并
+mod construct;
+
+nar_dev_utils::mods! {
+    // 转换
+    // * 🚩与「NAVM输出」一致，要求使用`serde`
+    "serde" => pub use conversion;
+
+    // JSON Schema
+    // * 🚩与「NAVM输出」一致，要求使用`serde_json`
+    "serde_json" => pub use schema;
 }
 
 /// 单元测试
@@ -38,4 +64,54 @@ mod tests {
             };
         }
     }
+
+    /// 测试/转换/含特殊字符的字符串参数
+    /// * 🎯任意字符串内容都能「格式化→解析」无损
+    #[test]
+    fn test_conversion_quoted() {
+        let strings = [
+            "",
+            "plain",
+            "/home/me/My Saves/a.json",
+            "C:\\Users\\me\\a.json",
+            "\"quoted\"",
+            "a\"b",
+            "tail\\",
+            "line\nbreak\ttab\r",
+            "  spaced  ",
+            "中文 路径",
+        ];
+        for s in strings {
+            let s = String::from(s);
+            let cmds = [
+                Cmd::SAV {
+                    target: s.clone().into(),
+                    path: s.clone(),
+                },
+                Cmd::LOA {
+                    target: "memory".into(),
+                    path: s.clone(),
+                },
+                Cmd::LOA {
+                    target: s.clone().into(),
+                    path: "".into(),
+                },
+                Cmd::RES {
+                    target: s.clone().into(),
+                },
+                Cmd::NEW { target: s.clone() },
+                Cmd::DEL { target: s.clone() },
+                Cmd::REG { name: s.clone() },
+                Cmd::INF {
+                    source: s.clone().into(),
+                },
+                Cmd::HLP { name: s.clone() },
+            ];
+            for cmd in cmds {
+                let cmd_str = cmd.to_string();
+                let reconverted = Cmd::parse(&cmd_str).expect("指令重解析失败");
+                assert_eq!(cmd, reconverted, "{cmd_str:?}");
+            }
+        }
+    }
 }
@@ -0,0 +1,301 @@
+//! 定义指令[`Cmd::NSE`]所用的「Narsese格式」
+//! * 🎯让使用者以ASCII之外的格式（LaTeX、漢文）输入、展示Narsese
+//! * 📌无论以何种格式解析，指令内部的词法Narsese**均统一为ASCII关键字**
+//!   * 📄漢文`「A是B」。`⇒词法Narsese中的系词为`-->`而非`是`
+//!   * 🎯保证JSON转换、能力检查、CIN转译等处无需关心输入格式
+//! * 🚩格式间的关键字对照表见下方常量，顺序与[`narsese`]中的格式定义一致
+//!
+//! [`Cmd::NSE`]: super::Cmd::NSE
+
+use super::{ParseError, ParseErrorKind, ParseResult, Span};
+use narsese::{
+    api::GetBudget,
+    conversion::string::impl_lexical::{
+        format_instances::{FORMAT_ASCII, FORMAT_HAN, FORMAT_LATEX},
+        NarseseFormat,
+    },
+    lexical::{Sentence as LexicalSentence, Task as LexicalTask, Term as LexicalTerm},
+};
+
+/// Narsese格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum NseFormat {
+    /// ASCII CommonNarsese
+    /// * 📌默认格式
+    #[default]
+    Ascii,
+    /// LaTeX扩展
+    LaTeX,
+    /// 漢文扩展
+    Han,
+    /// 自动识别
+    /// * 🚩解析时依次尝试ASCII、漢文、LaTeX，取首个成功者
+    /// * 🚩格式化时等同于ASCII
+    Auto,
+}
+
+/// 关键字对照表中的列：ASCII、LaTeX、漢文
+type KeywordRow = [&'static str; 3];
+
+/// 原子词项前缀
+const PREFIXES: &[KeywordRow] = &[
+    ["_", r"\diamond{}", "某"],
+    ["$", r"\$", "任一"],
+    ["#", r"\#", "其一"],
+    ["?", "?", "所问"],
+    ["+", "+", "间隔"],
+    ["^", r"\Uparrow{}", "操作"],
+];
+
+/// 复合词项连接符
+const CONNECTERS: &[KeywordRow] = &[
+    ["&", r"\cap{}", "外交"],
+    ["|", r"\cup{}", "内交"],
+    ["-", r"\minus{}", "外差"],
+    ["~", r"\sim{}", "内差"],
+    ["*", r"\times{}", "积"],
+    ["/", "/", "外像"],
+    [r"\", r"\backslash{}", "内像"],
+    ["&&", r"\wedge{}", "与"],
+    ["||", r"\vee{}", "或"],
+    ["--", r"\neg{}", "非"],
+    ["&/", ",", "接连"],
+    ["&|", ";", "同时"],
+];
+
+/// 词项集的括弧（左右分列）
+const SET_BRACKETS: &[KeywordRow] = &[
+    ["{", r"\left\{", "『"],
+    ["}", r"\right\}", "』"],
+    ["[", r"\left[", "【"],
+    ["]", r"\right]", "】"],
+];
+
+/// 陈述系词
+const COPULAS: &[KeywordRow] = &[
+    ["-->", r"\rightarrow{}", "是"],
+    ["<->", r"\leftrightarrow{}", "似"],
+    ["==>", r"\Rightarrow{}", "得"],
+    ["<=>", r"\Leftrightarrow{}", "同"],
+    ["{--", r"\circ\!\!\!\rightarrow{}", "为"],
+    ["--]", r"\rightarrow\!\!\!\circ{}", "有"],
+    ["{-]", r"\circ\!\!\!\rightarrow\!\!\!\circ{}", "具有"],
+    [r"=/>", r"/\!\!\!\!\!\Rightarrow{}", "将得"],
+    [r"=|>", r"|\!\!\!\!\!\Rightarrow{}", "现得"],
+    [r"=\>", r"\backslash\!\!\!\!\!\Rightarrow{}", "曾得"],
+    [r"</>", r"/\!\!\!\Leftrightarrow{}", "将同"],
+    [r"<|>", r"|\!\!\!\Leftrightarrow{}", "现同"],
+    [r"<\>", r"\backslash\!\!\!\Leftrightarrow{}", "曾同"],
+];
+
+/// 标点
+const PUNCTUATIONS: &[KeywordRow] = &[
+    [".", ".", "。"],
+    ["!", "!", "！"],
+    ["?", "?", "？"],
+    ["@", "¿", "；"],
+];
+
+/// 时态（固定时刻除外）
+const TENSES: &[KeywordRow] = &[
+    [r":\:", r"\backslash\!\!\!\!\!\Rightarrow{}", "过去"],
+    [r":|:", r"|\!\!\!\!\!\Rightarrow{}", "现在"],
+    [r":/:", r"/\!\!\!\!\!\Rightarrow{}", "将来"],
+];
+
+/// 固定时刻的前后缀
+const FIXED_STAMP: [(&str, &str); 3] = [(":!", ":"), ("t=", ""), ("发生在", "")];
+
+/// 将关键字从一种格式转换到另一种
+/// * 🚩不在表中⇒原样保留
+fn convert_keyword(table: &[KeywordRow], keyword: &str, from: usize, to: usize) -> String {
+    table
+        .iter()
+        .find(|row| row[from] == keyword)
+        .map_or(keyword, |row| row[to])
+        .to_owned()
+}
+
+/// 转换时间戳
+fn convert_stamp(stamp: &str, from: usize, to: usize) -> String {
+    let (from_left, from_right) = FIXED_STAMP[from];
+    let (to_left, to_right) = FIXED_STAMP[to];
+    match stamp
+        .strip_prefix(from_left)
+        .and_then(|rest| rest.strip_suffix(from_right))
+    {
+        Some(time) if !stamp.is_empty() => format!("{to_left}{time}{to_right}"),
+        _ => convert_keyword(TENSES, stamp, from, to),
+    }
+}
+
+/// 转换词项
+fn convert_term(term: &LexicalTerm, from: usize, to: usize) -> LexicalTerm {
+    let convert_terms = |terms: &[LexicalTerm]| {
+        terms
+            .iter()
+            .map(|term| convert_term(term, from, to))
+            .collect()
+    };
+    match term {
+        LexicalTerm::Atom { prefix, name } => LexicalTerm::Atom {
+            prefix: convert_keyword(PREFIXES, prefix, from, to),
+            name: name.clone(),
+        },
+        LexicalTerm::Compound { connecter, terms } => LexicalTerm::Compound {
+            connecter: convert_keyword(CONNECTERS, connecter, from, to),
+            terms: convert_terms(terms),
+        },
+        LexicalTerm::Set {
+            left_bracket,
+            terms,
+            right_bracket,
+        } => LexicalTerm::Set {
+            left_bracket: convert_keyword(SET_BRACKETS, left_bracket, from, to),
+            terms: convert_terms(terms),
+            right_bracket: convert_keyword(SET_BRACKETS, right_bracket, from, to),
+        },
+        LexicalTerm::Statement {
+            copula,
+            subject,
+            predicate,
+        } => LexicalTerm::Statement {
+            copula: convert_keyword(COPULAS, copula, from, to),
+            subject: Box::new(convert_term(subject, from, to)),
+            predicate: Box::new(convert_term(predicate, from, to)),
+        },
+    }
+}
+
+/// 转换语句
+fn convert_sentence(sentence: &LexicalSentence, from: usize, to: usize) -> LexicalSentence {
+    LexicalSentence {
+        term: convert_term(&sentence.term, from, to),
+        punctuation: convert_keyword(PUNCTUATIONS, &sentence.punctuation, from, to),
+        stamp: convert_stamp(&sentence.stamp, from, to),
+        truth: sentence.truth.clone(),
+    }
+}
+
+impl NseFormat {
+    /// 在关键字对照表中的列号
+    /// * 🚩自动识别⇒ASCII
+    fn column(&self) -> usize {
+        match self {
+            NseFormat::Ascii | NseFormat::Auto => 0,
+            NseFormat::LaTeX => 1,
+            NseFormat::Han => 2,
+        }
+    }
+
+    /// 对应的[`narsese`]格式
+    fn format_instance(&self) -> &'static NarseseFormat {
+        match self {
+            NseFormat::Ascii | NseFormat::Auto => &FORMAT_ASCII,
+            NseFormat::LaTeX => &FORMAT_LATEX,
+            NseFormat::Han => &FORMAT_HAN,
+        }
+    }
+
+    /// 将ASCII关键字的词法任务转换到此格式
+    pub fn from_ascii(&self, task: &LexicalTask) -> LexicalTask {
+        LexicalTask {
+            budget: task.budget.clone(),
+            sentence: convert_sentence(&task.sentence, 0, self.column()),
+        }
+    }
+
+    /// 将此格式关键字的词法任务转换为ASCII关键字
+    pub fn to_ascii(&self, task: &LexicalTask) -> LexicalTask {
+        LexicalTask {
+            budget: task.budget.clone(),
+            sentence: convert_sentence(&task.sentence, self.column(), 0),
+        }
+    }
+
+    /// 以此格式解析Narsese任务
+    /// * 🚩语句将被转换为「空预算任务」
+    /// * 📌解析结果中的关键字统一为ASCII
+    /// * 📌报错时的出错范围为`span`
+    pub fn parse_task(&self, narsese: &str, span: Span) -> ParseResult<LexicalTask> {
+        match self {
+            // 依次尝试，取首个成功者；均失败⇒报ASCII的错
+            NseFormat::Auto => {
+                let ascii_error = match NseFormat::Ascii.parse_task(narsese, span.clone()) {
+                    Ok(task) => return Ok(task),
+                    Err(e) => e,
+                };
+                [NseFormat::Han, NseFormat::LaTeX]
+                    .into_iter()
+                    .find_map(|format| format.parse_task(narsese, span.clone()).ok())
+                    .ok_or(ascii_error)
+            }
+            _ => {
+                let parsed = self
+                    .format_instance()
+                    .parse(narsese)
+                    .map_err(|e| ParseError::new(ParseErrorKind::NarseseSyntax(e), span.clone()))?;
+                // * ⚠️其中的「语句」将会被转换为「空预算任务」
+                let task = parsed.try_into_task_compatible().map_err(|e| {
+                    ParseError::new(ParseErrorKind::NarseseNotTask(e.to_string()), span)
+                })?;
+                Ok(self.to_ascii(&task))
+            }
+        }
+    }
+
+    /// 以此格式格式化（ASCII关键字的）词法任务
+    /// * 🚩空预算⇒只格式化其中的语句
+    pub fn format_task(&self, task: &LexicalTask) -> String {
+        let format = self.format_instance();
+        let task = match self.column() {
+            0 => task.clone(),
+            _ => self.from_ascii(task),
+        };
+        match task.get_budget().is_empty() {
+            true => format.format(&task.sentence),
+            false => format.format(&task),
+        }
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nar_dev_utils::asserts;
+    use narsese::lexical_nse_task;
+
+    #[test]
+    fn test_nse_format() {
+        // * ⚠️`{-]`等无法放进宏中：括号不配对
+        let tasks = [
+            "<A --> B>.",
+            "$0.5;0.5;0.5$ <(&&, <$x --> [B]>, (*, {SELF}, #y)) ==> <$x <-> ^op>>! :|: %1.0;0.9%",
+            "<(&/, (--, a), (|, b, c)) =/> <d {-] e>>? :!-12:",
+        ]
+        .map(|s| NseFormat::Ascii.parse_task(s, 0..0).unwrap());
+        for format in [NseFormat::Ascii, NseFormat::LaTeX, NseFormat::Han] {
+            for task in &tasks {
+                let formatted = format.format_task(task);
+                println!("{formatted}");
+                // 格式化→解析 无损
+                let reparsed = format.parse_task(&formatted, 0..0).expect("重解析失败");
+                assert_eq!(*task, reparsed, "{format:?}: {formatted}");
+                // 自动识别
+                let auto = NseFormat::Auto.parse_task(&formatted, 0..0).expect("识别失败");
+                assert_eq!(*task, auto, "{format:?}: {formatted}");
+            }
+        }
+        asserts! {
+            NseFormat::Han.parse_task("「A是B」。", 0..0).unwrap() => lexical_nse_task!(<A --> B>.)
+            NseFormat::Auto.format_task(&tasks[0]) => "<A --> B>."
+        }
+        // 均失败⇒ASCII的错误
+        let e = NseFormat::Auto.parse_task("<A --> B", 4..12).unwrap_err();
+        asserts! {
+            matches!(e.kind(), ParseErrorKind::NarseseSyntax(..)) => true
+            e.span() => 4..12
+        }
+    }
+}
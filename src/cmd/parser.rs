@@ -1,138 +1,377 @@
 //! 定义「NAVM指令」的简易解析器
 //! * 从字符串简要解析出NAVM指令指令类型
 
-use super::Cmd;
-use nar_dev_utils::{if_return, pipe};
-use narsese::conversion::string::impl_lexical::format_instances::FORMAT_ASCII;
-use std::{error::Error, fmt::Display};
+use super::{Cmd, CmdRegistry, NseFormat};
+use nar_dev_utils::if_return;
+use std::{error::Error, fmt::Display, ops::Range};
 
 /// 固定的「空字串」常量
 /// * 📝定长数组非Copy初始化：如果需要在定长数组中初始化一个方法，应该先声明一个const，然后从中初始化
 const EMPTY_STRING: std::string::String = String::new();
 
+/// 「指令参数」在其所在行中的字节范围
+/// * 🎯报错时标出出错的参数
+pub type Span = Range<usize>;
+
+/// 参数引号
+/// * 🎯包裹含空白符等特殊字符的参数
+pub const PARAM_QUOTE: char = '"';
+
+/// 参数转义符
+/// * ⚠️仅在引号内生效：引号外的`\`保持原样（兼容Windows路径）
+pub const PARAM_ESCAPE: char = '\\';
+
+/// 引号内的转义字符→实际字符
+/// * 📌未知的转义字符保持自身，如`\\`→`\`、`\"`→`"`
+fn unescape_char(c: char) -> char {
+    match c {
+        'n' => '\n',
+        'r' => '\r',
+        't' => '\t',
+        c => c,
+    }
+}
+
+/// 按空白符拆分参数，并附带各参数的字节范围
+/// * 📌范围相对于传入的字串`s`
+/// * ✨类shell的引号语法：以引号开头的参数，读取到闭合引号为止
+///   * 📄`SAV memory "/home/me/My Saves/a.json"`
+///   * 📌引号内可使用转义：`\\` `\"` `\n` `\r` `\t`
+///   * 📌闭合引号之后的非空白字符，仍拼接到同一参数中
+fn split_params_spanned(s: &str) -> ParseResult<Vec<(String, Span)>> {
+    let mut result = vec![];
+    let mut chars = s.char_indices().peekable();
+    loop {
+        // 跳过空白符
+        while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+        // 参数起始位置
+        let Some(&(start, _)) = chars.peek() else {
+            break;
+        };
+        let mut param = String::new();
+        while let Some((i, c)) = chars.next_if(|(_, c)| !c.is_whitespace()) {
+            match c {
+                // 仅在参数开头识别引号
+                PARAM_QUOTE if i == start => {
+                    let unterminated =
+                        || ParseError::new(ParseErrorKind::UnterminatedQuote, start..s.len());
+                    loop {
+                        match chars.next().ok_or_else(unterminated)? {
+                            (_, PARAM_QUOTE) => break,
+                            (_, PARAM_ESCAPE) => {
+                                let (_, c) = chars.next().ok_or_else(unterminated)?;
+                                param.push(unescape_char(c))
+                            }
+                            (_, c) => param.push(c),
+                        }
+                    }
+                }
+                c => param.push(c),
+            }
+        }
+        // 参数结束位置：下一个空白符或行尾
+        let end = chars.peek().map_or(s.len(), |(i, _)| *i);
+        result.push((param, start..end));
+    }
+    Ok(result)
+}
+
 /// 封装「获取N个命令参数」的功能
 /// * 🚩【2024-07-02 01:25:18】目前提取出两个函数的共同逻辑，其差异通过闭包体现
+/// * ✨同时返回各参数的字节范围
 #[inline(always)]
 fn _get_cmd_params<const N: usize>(
     s: &str,
-    split_next_handler: impl Fn(Option<&str>) -> Result<&str, ParseError>,
-) -> ParseResult<[String; N]> {
+    split_next_handler: impl Fn(usize, Option<(String, Span)>) -> ParseResult<(String, Span)>,
+) -> ParseResult<[(String, Span); N]> {
     // 先拆分空格（连续空格缩并）
-    let mut split = s.split_whitespace();
+    let mut split = split_params_spanned(s)?.into_iter();
 
     // 初始化，拷贝N个空字串
-    let mut result: [String; N] = [EMPTY_STRING; N];
-    for result_i in &mut result {
-        pipe! {
-            split.next() // 取下一个参数
-            => split_next_handler // 拆分下一个参数：取默认值，或报错
-            => {?}# // 错误上报
-            => [result_i.push_str] // 加入参数
-        };
+    let mut result: [(String, Span); N] = std::array::from_fn(|_| (EMPTY_STRING, 0..0));
+    for (i, result_i) in result.iter_mut().enumerate() {
+        // 取下一个参数：取默认值，或报错
+        *result_i = split_next_handler(i, split.next())?;
     }
     // 开始拆分：过长⇒忽略，过短⇒报错
     Ok(result)
 }
-/// 封装「获取N个命令参数」的功能
-fn get_cmd_params<const N: usize>(s: &str) -> ParseResult<[String; N]> {
-    _get_cmd_params(s, |s| {
-        // 在「遇到空值」时报错
-        s.ok_or_else(|| ParseError(format!("参数个数不足{N}个！")))
+
+/// 封装「获取N个命令参数」的功能，附带参数范围
+/// * 🎯亦供扩展指令的解析器使用
+pub fn get_cmd_params_spanned<const N: usize>(s: &str) -> ParseResult<[(String, Span); N]> {
+    _get_cmd_params(s, |i, param| {
+        // 在「遇到空值」时报错 | 范围标在行尾
+        param.ok_or_else(|| {
+            ParseError::new(
+                ParseErrorKind::MissingArgument {
+                    index: i,
+                    expected: N,
+                },
+                s.len()..s.len(),
+            )
+        })
     })
 }
 
+/// 封装「获取N个命令参数」的功能
+pub fn get_cmd_params<const N: usize>(s: &str) -> ParseResult<[String; N]> {
+    Ok(get_cmd_params_spanned::<N>(s)?.map(|(param, _)| param))
+}
+
 /// 封装「获取N个命令参数」的功能，但对空值取空字串
-fn get_cmd_params_loose<const N: usize>(s: &str) -> ParseResult<[String; N]> {
+pub fn get_cmd_params_loose<const N: usize>(s: &str) -> ParseResult<[String; N]> {
     // 在参数缺省时取空字串
-    _get_cmd_params(s, |s| Ok(s.unwrap_or("")))
+    let params = _get_cmd_params::<N>(s, |_, param| {
+        Ok(param.unwrap_or((EMPTY_STRING, s.len()..s.len())))
+    })?;
+    Ok(params.map(|(param, _)| param))
+}
+
+/// 获取一个数值参数
+/// * 🎯`CYC`、`VOL`等指令
+/// * 🚩数值解析失败时，范围标在该参数上
+pub fn get_cmd_param_usize(s: &str) -> ParseResult<usize> {
+    let [(num_str, span)] = get_cmd_params_spanned::<1>(s)?;
+    num_str
+        .parse::<usize>()
+        .map_err(|e| ParseError::new(ParseErrorKind::InvalidNumber(e), span))
 }
 
 /// 封装「指令解析结果」相关功能
 mod parse_error {
     use super::*;
+    use narsese::conversion::string::impl_lexical::ParseError as NarseseParseError;
+    use std::{num::ParseIntError, sync::Arc};
+
+    /// 解析错误的种类
+    /// * 🎯让调用者无需匹配字符串即可区分出错原因
+    #[derive(Debug, Clone)]
+    pub enum ParseErrorKind {
+        /// 尝试解析空行
+        EmptyLine,
+
+        /// 参数个数不足
+        MissingArgument {
+            /// 缺失参数的索引（从0开始）
+            index: usize,
+            /// 该指令所需的参数个数
+            expected: usize,
+        },
+
+        /// 数值参数解析失败
+        /// * 📄`CYC a`
+        InvalidNumber(ParseIntError),
+
+        /// 参数的引号未闭合
+        /// * 📄`SAV memory "a b`
+        UnterminatedQuote,
+
+        /// Narsese语法错误
+        /// * 🚩携带来自[`narsese`]的解析错误
+        NarseseSyntax(NarseseParseError),
+
+        /// Narsese能解析，但无法作为任务输入
+        /// * 📄`NSE A`：只有词项，没有标点
+        NarseseNotTask(String),
+
+        /// 参数不合法
+        /// * 🎯扩展指令等自定义解析器的通用错误
+        /// * 📄`*motorbabbling abc`
+        InvalidArgument(String),
+
+        /// 行过长
+        /// * 🎯流式读取时，跳过超出上限的行
+        LineTooLong {
+            /// 单行长度上限（字节）
+            max: usize,
+        },
+
+        /// 行不是合法的UTF-8
+        InvalidUtf8,
+
+        /// 读取错误
+        /// * 🚩使用[`Arc`]以便克隆
+        Io(Arc<std::io::Error>),
+    }
+
+    impl Display for ParseErrorKind {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            match self {
+                ParseErrorKind::EmptyLine => write!(f, "尝试解析空行！"),
+                ParseErrorKind::MissingArgument { index, expected } => {
+                    write!(f, "参数个数不足{expected}个！缺少第{}个参数", index + 1)
+                }
+                ParseErrorKind::InvalidNumber(e) => write!(f, "数值参数解析失败：{e}"),
+                ParseErrorKind::UnterminatedQuote => write!(f, "参数的引号未闭合！"),
+                ParseErrorKind::NarseseSyntax(e) => write!(f, "{e}"),
+                ParseErrorKind::NarseseNotTask(message) => {
+                    write!(f, "Narsese无法作为任务输入：{message}")
+                }
+                ParseErrorKind::InvalidArgument(message) => write!(f, "参数不合法：{message}"),
+                ParseErrorKind::LineTooLong { max } => write!(f, "行过长！超出上限{max}字节"),
+                ParseErrorKind::InvalidUtf8 => write!(f, "行不是合法的UTF-8！"),
+                ParseErrorKind::Io(e) => write!(f, "读取错误：{e}"),
+            }
+        }
+    }
 
     /// 解析错误的类型
-    #[derive(Debug)]
-    pub struct ParseError(pub String);
+    /// * 🚩种类+出错范围
+    /// * 📌范围为**字节范围**，相对于传入[`Cmd::parse`]的整行
+    ///   * 🎯便于编辑器等标出出错位置
+    #[derive(Debug, Clone)]
+    pub struct ParseError {
+        /// 错误种类
+        kind: ParseErrorKind,
+        /// 出错范围
+        span: Span,
+    }
 
     impl ParseError {
-        pub fn new(s: &str) -> ParseError {
-            ParseError(s.to_string())
+        /// 构造函数
+        pub fn new(kind: ParseErrorKind, span: Span) -> ParseError {
+            ParseError { kind, span }
+        }
+
+        /// 获取错误种类
+        pub fn kind(&self) -> &ParseErrorKind {
+            &self.kind
+        }
+
+        /// 获取出错范围
+        pub fn span(&self) -> Span {
+            self.span.clone()
+        }
+
+        /// 将出错范围整体后移
+        /// * 🎯从「参数行」中的范围，转换到「整行」中的范围
+        pub fn offset(mut self, offset: usize) -> ParseError {
+            self.span = self.span.start + offset..self.span.end + offset;
+            self
         }
     }
     impl Display for ParseError {
         fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-            write!(f, "NAVM Cmd ParseError: {}", self.0)
+            write!(
+                f,
+                "NAVM Cmd ParseError: {} @ {}..{}",
+                self.kind, self.span.start, self.span.end
+            )
         }
     }
     impl Error for ParseError {
-        // 本身就只包含描述
-        fn description(&self) -> &str {
-            &self.0
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match &self.kind {
+                ParseErrorKind::InvalidNumber(e) => Some(e),
+                ParseErrorKind::NarseseSyntax(e) => Some(e),
+                ParseErrorKind::Io(e) => Some(&**e),
+                _ => None,
+            }
         }
     }
 
-    /// * 🎯从其它「错误」类型转换到「解析错误」
-    /// * ⚠️实现[`From`]特征会起冲突
-    pub fn to_parse_error(e: impl ToString) -> ParseError {
-        ParseError(e.to_string())
-    }
-
     /// 简记的类型别名：解析结果
     pub type ParseResult<T> = Result<T, ParseError>;
 }
-use parse_error::*;
+pub use parse_error::*;
+
+/// 解析选项
+/// * 🎯在「默认解析」之外，定制Narsese格式、扩展指令等
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    /// 指令[`Cmd::NSE`]所用的Narsese格式
+    pub nse_format: NseFormat,
+    /// 扩展指令注册表
+    pub registry: CmdRegistry,
+}
 
 /// 扩展指令[`Cmd`]类型的功能
 impl super::Cmd {
     /// 从字符串构造NAVM指令
     pub fn parse(line: &str) -> ParseResult<Self> {
+        Self::parse_line(line, &CmdRegistry::default(), NseFormat::Ascii)
+    }
+
+    /// 从字符串构造NAVM指令，并识别其中注册过的扩展指令
+    /// * 🚩指令头在注册表中⇒交由注册的解析器，产生[`Cmd::Ext`]
+    ///   * ⚠️注册表优先于内置指令
+    /// * 🚩否则⇒与[`Cmd::parse`]一致
+    pub fn parse_with(line: &str, registry: &CmdRegistry) -> ParseResult<Self> {
+        Self::parse_line(line, registry, NseFormat::Ascii)
+    }
+
+    /// 按解析选项，从字符串构造NAVM指令
+    /// * 🔗[`Cmd::parse_with`]与[`Cmd::parse_str_params_in`]的结合
+    pub fn parse_with_options(line: &str, options: &ParseOptions) -> ParseResult<Self> {
+        Self::parse_line(line, &options.registry, options.nse_format)
+    }
+
+    /// 从字符串构造NAVM指令的内部实现
+    fn parse_line(line: &str, registry: &CmdRegistry, nse_format: NseFormat) -> ParseResult<Self> {
         // 空字串
         if_return! {
-            line.trim().is_empty() => Err(ParseError::new("尝试解析空行！"))
+            line.trim().is_empty() => Err(ParseError::new(ParseErrorKind::EmptyLine, 0..line.len()))
         }
         // 拆分字符串为两个部分
         // * 📜默认情况：整个指令都是指令头（无参数）
         let (head, params) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
-        // 构造指令
-        Self::parse_str_params(head, params)
+        // 参数行在整行中的起始位置
+        let params_offset = line.len() - params.len();
+        // 构造指令 | 出错范围转换到整行中
+        let result = match registry.parse_ext(head, params) {
+            Some(result) => result.map(Cmd::Ext),
+            None => Self::parse_str_params_in(head, params, nse_format),
+        };
+        result.map_err(|e| e.offset(params_offset))
     }
 
     /// 从字符串参数中构造NAVM指令
     /// * 🚩除了「指令头」以外，均为「指令行」
     ///   * ⚠️「指令行」不包括「指令头」
+    /// * 📌报错时的出错范围相对于「指令行」
     pub fn parse_str_params(head: &str, line: &str) -> ParseResult<Self> {
+        Self::parse_str_params_in(head, line, NseFormat::Ascii)
+    }
+
+    /// 从字符串参数中构造NAVM指令，并指定[`Cmd::NSE`]所用的Narsese格式
+    /// * 🔗[`Cmd::parse_str_params`]
+    pub fn parse_str_params_in(head: &str, line: &str, nse_format: NseFormat) -> ParseResult<Self> {
         Ok(match head.to_uppercase().as_str() {
             // 内置：各自有各自的处理方法
             "SAV" => {
                 // 以空格分隔
                 let [target, path] = get_cmd_params_loose::<2>(line)?;
-                Cmd::SAV { target, path }
+                Cmd::SAV {
+                    target: target.into(),
+                    path,
+                }
             }
             "LOA" => {
                 // 以空格分隔
                 let [target, path] = get_cmd_params_loose::<2>(line)?;
-                Cmd::LOA { target, path }
+                Cmd::LOA {
+                    target: target.into(),
+                    path,
+                }
             }
             "RES" => {
                 // 以空格分隔 | 此处为「松弛获取」：缺省的参数允许填充空格
                 let [target] = get_cmd_params_loose::<1>(line)?;
-                Cmd::RES { target }
+                Cmd::RES {
+                    target: target.into(),
+                }
             }
             "NSE" => {
                 // 🚩以CommonNarsese ASCII语法解析出「词法Narsese」
                 // * 📌此处旨在统一格式，如`NSE <A --> B>.`
                 // * 📌【2024-03-22 17:45:47】至于「转换为子程序输入」的形式，这是留给后续运行时的
-                let narsese = FORMAT_ASCII
-                    // 尝试解析
-                    .parse(line)
-                    // 转换其中的错误类型
-                    .map_err(to_parse_error)?;
-                // 尝试进行隐式转换，以统一使用`Task`类型
+                // * 📌出错范围：去掉首尾空白后的整个参数行
+                let start = line.len() - line.trim_start().len();
+                let span = start..start + line.trim().len();
+                // 🚩【2026-10-17 04:13:42】可选格式；解析结果的关键字统一为ASCII
                 // * ⚠️其中的「语句」将会被转换为「空预算任务」
-                let task = narsese.try_into_task_compatible().map_err(to_parse_error)?;
-                // 返回
-                Cmd::NSE(task)
+                Cmd::NSE(nse_format.parse_task(line, span)?)
             }
             "NEW" => {
                 // 以空格分隔
@@ -146,14 +385,12 @@ impl super::Cmd {
             }
             "CYC" => {
                 // 以空格分隔
-                let [num_str] = get_cmd_params::<1>(line)?;
-                let num = num_str.parse::<usize>().map_err(to_parse_error)?;
+                let num = get_cmd_param_usize(line)?;
                 Cmd::CYC(num)
             }
             "VOL" => {
                 // 以空格分隔
-                let [num_str] = get_cmd_params::<1>(line)?;
-                let num = num_str.parse::<usize>().map_err(to_parse_error)?;
+                let num = get_cmd_param_usize(line)?;
                 Cmd::VOL(num)
             }
             "REG" => {
@@ -164,7 +401,9 @@ impl super::Cmd {
             "INF" => {
                 // 以空格分隔
                 let [source] = get_cmd_params_loose::<1>(line)?;
-                Cmd::INF { source }
+                Cmd::INF {
+                    source: source.into(),
+                }
             }
             "HLP" => {
                 // 以空格分隔 | 此处为「松弛获取」：缺省的参数允许填充空格
@@ -190,6 +429,7 @@ impl super::Cmd {
 #[cfg(test)]
 mod test {
     use super::*;
+    use nar_dev_utils::asserts;
 
     #[test]
     fn test_split_ascii_whitespace() {
@@ -259,4 +499,67 @@ mod test {
             ",
         )
     }
+
+    /// 测试/解析错误的种类与范围
+    /// * 🎯出错时能区分错误种类，并标出出错的位置
+    #[test]
+    fn test_parse_error() {
+        // 空行
+        let e = Cmd::parse("   ").unwrap_err();
+        assert!(matches!(e.kind(), ParseErrorKind::EmptyLine));
+        assert_eq!(e.span(), 0..3);
+        // 参数不足 | 范围标在行尾
+        let e = Cmd::parse("NEW").unwrap_err();
+        assert!(matches!(
+            e.kind(),
+            ParseErrorKind::MissingArgument {
+                index: 0,
+                expected: 1
+            }
+        ));
+        assert_eq!(e.span(), 3..3);
+        // 数值错误 | 范围标在参数上
+        let line = "CYC  abc";
+        let e = Cmd::parse(line).unwrap_err();
+        assert!(matches!(e.kind(), ParseErrorKind::InvalidNumber(..)));
+        assert_eq!(&line[e.span()], "abc");
+        // Narsese语法错误
+        let line = "NSE <A --> B. ";
+        let e = Cmd::parse(line).unwrap_err();
+        assert!(matches!(e.kind(), ParseErrorKind::NarseseSyntax(..)));
+        assert_eq!(&line[e.span()], "<A --> B.");
+        // Narsese不是任务
+        let e = Cmd::parse("NSE A").unwrap_err();
+        assert!(matches!(e.kind(), ParseErrorKind::NarseseNotTask(..)));
+        // 错误信息可展示
+        println!("{e}");
+    }
+
+    /// 测试/解析/引号与转义
+    #[test]
+    fn test_parse_quoted() {
+        asserts! {
+            Cmd::parse(r#"SAV memory "/home/me/My Saves/a.json""#).unwrap() => Cmd::SAV {
+                target: "memory".into(),
+                path: "/home/me/My Saves/a.json".into(),
+            }
+            Cmd::parse(r#"LOA "" C:\a\b.json"#).unwrap() => Cmd::LOA {
+                target: "".into(),
+                path: r"C:\a\b.json".into(),
+            }
+            Cmd::parse(r#"INF "a \"b\" \\ \n""#).unwrap() => Cmd::INF {
+                source: "a \"b\" \\ \n".into(),
+            }
+            // 引号之后的字符仍属同一参数；参数中间的引号保持原样
+            Cmd::parse(r#"REG "a b"c"d"#).unwrap() => Cmd::REG {
+                name: r#"a bc"d"#.into(),
+            }
+        }
+        // 引号未闭合
+        for line in [r#"SAV memory "a b"#, r#"NEW "a\"#] {
+            let e = Cmd::parse(line).unwrap_err();
+            assert!(matches!(e.kind(), ParseErrorKind::UnterminatedQuote));
+            assert_eq!(e.span().end, line.len());
+        }
+    }
 }
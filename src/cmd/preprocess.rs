@@ -0,0 +1,618 @@
+//! 定义「NAVM脚本」的预处理器
+//! * 🎯减少测试脚本中的复制粘贴：共用的背景知识、手动展开的循环
+//! * 📌在[「NAVM脚本」](super::script)语法（注释、续行）之上，增加以`@`开头的预处理指令
+//!   * `@include <路径>`：引入另一脚本文件（路径相对于当前文件所在目录）
+//!   * `@repeat <次数> [变量名]` … `@end`：计数重复块，可嵌套；可选地绑定从0开始的序号
+//!   * `@set <变量名> <值>`：定义变量，值为该行其余内容
+//!   * `${变量名}`：在其后的指令、预处理指令参数中替换为变量值
+//!     * ⚠️不使用`$变量名`：`$`在Narsese中表示「独立变量」与「预算值」
+//! * 📌展开结果为「指令+来源（文件、行号）」的列表
+//! * 🚩遇到首个错误即停止
+//!   * 📌不同于[`CmdScript`](super::CmdScript)：重复块会令同一错误反复出现
+
+use super::{logical_lines, Cmd, ParseError, ParseOptions};
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{Debug, Display},
+    io,
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+
+/// 预处理指令的前缀
+pub const PREPROCESS_PREFIX: char = '@';
+
+/// 引入文件的最大嵌套层数
+/// * 🎯兜底：即便循环检测失效（如符号链接），也不会无限递归
+pub const MAX_INCLUDE_DEPTH: usize = 64;
+
+/// 指令的来源
+/// * 🚩文件+行号（从1开始）
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SourceLoc {
+    /// 所在文件
+    pub file: PathBuf,
+    /// 所在行号（从1开始；续行取其首行）
+    pub line_no: usize,
+}
+
+impl Display for SourceLoc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.file.display(), self.line_no)
+    }
+}
+
+/// 展开后的指令
+/// * 🚩指令+来源
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpandedCmd {
+    /// 展开（变量替换）后解析出的指令
+    pub cmd: Cmd,
+    /// 指令在源文件中的位置
+    pub source: SourceLoc,
+}
+
+/// 预处理错误的种类
+#[derive(Debug, Clone)]
+pub enum PreprocessErrorKind {
+    /// 指令解析错误
+    Parse(ParseError),
+    /// 文件读取错误
+    /// * 🚩使用[`Arc`]以便克隆
+    Io { path: PathBuf, error: Arc<io::Error> },
+    /// 未知的预处理指令
+    UnknownDirective(String),
+    /// 预处理指令缺少参数
+    MissingArgument(&'static str),
+    /// 重复次数不合法
+    InvalidRepeatCount(String),
+    /// 多余的`@end`
+    UnmatchedEnd,
+    /// 未以`@end`结束的重复块
+    UnclosedRepeat,
+    /// 未定义的变量
+    UndefinedVariable(String),
+    /// 循环引入
+    IncludeCycle(PathBuf),
+    /// 引入层数过深
+    /// * 🔗[`MAX_INCLUDE_DEPTH`]
+    IncludeTooDeep(PathBuf),
+}
+
+impl Display for PreprocessErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse(e) => write!(f, "{e}"),
+            Self::Io { path, error } => write!(f, "无法读取文件「{}」：{error}", path.display()),
+            Self::UnknownDirective(name) => write!(f, "未知的预处理指令「@{name}」"),
+            Self::MissingArgument(name) => write!(f, "预处理指令「@{name}」缺少参数"),
+            Self::InvalidRepeatCount(count) => write!(f, "重复次数「{count}」不合法"),
+            Self::UnmatchedEnd => write!(f, "多余的「@end」"),
+            Self::UnclosedRepeat => write!(f, "重复块未以「@end」结束"),
+            Self::UndefinedVariable(name) => write!(f, "未定义的变量「{name}」"),
+            Self::IncludeCycle(path) => write!(f, "循环引入文件「{}」", path.display()),
+            Self::IncludeTooDeep(path) => write!(
+                f,
+                "引入文件「{}」时超出最大嵌套层数{MAX_INCLUDE_DEPTH}",
+                path.display()
+            ),
+        }
+    }
+}
+
+/// 预处理错误
+/// * 🚩种类+出错位置
+#[derive(Debug, Clone)]
+pub struct PreprocessError {
+    /// 错误种类
+    pub kind: PreprocessErrorKind,
+    /// 出错位置
+    pub source: SourceLoc,
+}
+
+impl Display for PreprocessError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.source, self.kind)
+    }
+}
+
+impl Error for PreprocessError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            PreprocessErrorKind::Parse(e) => Some(e),
+            PreprocessErrorKind::Io { error, .. } => Some(&**error),
+            _ => None,
+        }
+    }
+}
+
+/// 简记的类型别名：预处理结果
+pub type PreprocessResult<T> = Result<T, PreprocessError>;
+
+/// 脚本中的一项（已分块）
+#[derive(Debug, Clone)]
+enum Item {
+    /// 普通指令行
+    Line { line_no: usize, text: String },
+    /// `@set`
+    Set {
+        line_no: usize,
+        name: String,
+        value: String,
+    },
+    /// `@include`
+    Include { line_no: usize, path: String },
+    /// `@repeat` … `@end`
+    Repeat {
+        line_no: usize,
+        count: String,
+        var: Option<String>,
+        body: Vec<Item>,
+    },
+}
+
+/// 尚未配对`@end`的`@repeat`：（行号，次数，序号变量名）
+type OpenRepeat = (usize, String, Option<String>);
+
+/// 文件读取函数
+type Loader = Box<dyn Fn(&Path) -> io::Result<String>>;
+
+/// 脚本预处理器
+/// * 🚩默认从文件系统读取被引入的文件
+pub struct Preprocessor {
+    /// 文件读取函数
+    loader: Loader,
+    /// 指令解析选项
+    parse_options: ParseOptions,
+}
+
+impl Debug for Preprocessor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Preprocessor")
+            .field("parse_options", &self.parse_options)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for Preprocessor {
+    fn default() -> Self {
+        Self::with_loader(|path| std::fs::read_to_string(path))
+    }
+}
+
+/// 展开过程中的状态
+struct Expander<'a> {
+    /// 预处理器
+    preprocessor: &'a Preprocessor,
+    /// 变量表
+    /// * 📌全局共享：被引入的文件中定义的变量，对引入者可见
+    variables: HashMap<String, String>,
+    /// 正在展开的文件（用于检测循环引入）
+    file_stack: Vec<PathBuf>,
+    /// 展开结果
+    result: Vec<ExpandedCmd>,
+}
+
+impl Preprocessor {
+    /// 构造函数
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 以自定义的文件读取函数构造
+    /// * 🎯从内存、压缩包等处读取被引入的文件
+    pub fn with_loader(loader: impl Fn(&Path) -> io::Result<String> + 'static) -> Self {
+        Self {
+            loader: Box::new(loader),
+            parse_options: ParseOptions::default(),
+        }
+    }
+
+    /// 设置指令解析选项
+    pub fn with_parse_options(mut self, parse_options: ParseOptions) -> Self {
+        self.parse_options = parse_options;
+        self
+    }
+
+    /// 读取并展开脚本文件
+    pub fn expand_file(&self, path: impl AsRef<Path>) -> PreprocessResult<Vec<ExpandedCmd>> {
+        let mut expander = Expander::new(self);
+        expander.expand_file(path.as_ref(), None)?;
+        Ok(expander.result)
+    }
+
+    /// 展开脚本文本
+    /// * 📌`file`用作来源信息，以及被引入文件的相对路径基准
+    pub fn expand_str(
+        &self,
+        src: &str,
+        file: impl AsRef<Path>,
+    ) -> PreprocessResult<Vec<ExpandedCmd>> {
+        let mut expander = Expander::new(self);
+        expander.expand_src(src, file.as_ref())?;
+        Ok(expander.result)
+    }
+}
+
+/// 规范化路径
+/// * 🎯使循环检测不受`sub/../a.navm`、`./a.navm`等写法影响
+/// * 🚩仅做字面处理：去除`.`、抵消`..`
+///   * 📌不访问文件系统：被引入的文件可能来自自定义的读取函数
+///   * ⚠️无法识别符号链接⇒另有[`MAX_INCLUDE_DEPTH`]兜底
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut result = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            // 可抵消⇒弹出上一级；否则（开头、已是`..`）保留
+            Component::ParentDir => match result.components().next_back() {
+                Some(Component::Normal(..)) => {
+                    result.pop();
+                }
+                Some(Component::RootDir | Component::Prefix(..)) => {}
+                _ => result.push(component),
+            },
+            _ => result.push(component),
+        }
+    }
+    result
+}
+
+/// 只保留展开结果中的指令
+pub fn into_cmds(expanded: impl IntoIterator<Item = ExpandedCmd>) -> Vec<Cmd> {
+    expanded.into_iter().map(|ExpandedCmd { cmd, .. }| cmd).collect()
+}
+
+/// 拆分出「首个空白符前」与「之后（去除首尾空白）」两部分
+fn split_word(s: &str) -> (&str, &str) {
+    let s = s.trim();
+    match s.split_once(char::is_whitespace) {
+        Some((first, rest)) => (first, rest.trim()),
+        None => (s, ""),
+    }
+}
+
+/// 将逻辑行分块
+/// * 🚩识别预处理指令，并将`@repeat`与`@end`配对
+fn parse_items(file: &Path, lines: Vec<(usize, String)>) -> PreprocessResult<Vec<Item>> {
+    let error = |kind, line_no| PreprocessError {
+        kind,
+        source: SourceLoc {
+            file: file.to_owned(),
+            line_no,
+        },
+    };
+    // 栈：（`@repeat`的信息，外层已收集的项）；栈底为顶层
+    let mut stack: Vec<(Option<OpenRepeat>, Vec<Item>)> = vec![(None, vec![])];
+    for (line_no, text) in lines {
+        let Some(directive) = text.strip_prefix(PREPROCESS_PREFIX) else {
+            stack.last_mut().unwrap().1.push(Item::Line { line_no, text });
+            continue;
+        };
+        let (name, args) = split_word(directive);
+        let item = match name {
+            "include" => match args {
+                "" => return Err(error(PreprocessErrorKind::MissingArgument("include"), line_no)),
+                path => Item::Include {
+                    line_no,
+                    path: path.to_owned(),
+                },
+            },
+            "set" => match split_word(args) {
+                ("", _) => return Err(error(PreprocessErrorKind::MissingArgument("set"), line_no)),
+                (name, value) => Item::Set {
+                    line_no,
+                    name: name.to_owned(),
+                    value: value.to_owned(),
+                },
+            },
+            "repeat" => {
+                let (count, var) = split_word(args);
+                if count.is_empty() {
+                    return Err(error(PreprocessErrorKind::MissingArgument("repeat"), line_no));
+                }
+                let var = (!var.is_empty()).then(|| var.to_owned());
+                stack.push((Some((line_no, count.to_owned(), var)), vec![]));
+                continue;
+            }
+            "end" => match stack.pop() {
+                Some((Some((line_no, count, var)), body)) => Item::Repeat {
+                    line_no,
+                    count,
+                    var,
+                    body,
+                },
+                _ => return Err(error(PreprocessErrorKind::UnmatchedEnd, line_no)),
+            },
+            name => {
+                return Err(error(
+                    PreprocessErrorKind::UnknownDirective(name.to_owned()),
+                    line_no,
+                ))
+            }
+        };
+        stack.last_mut().unwrap().1.push(item);
+    }
+    match stack.pop() {
+        Some((None, items)) => Ok(items),
+        Some((Some((line_no, ..)), _)) => Err(error(PreprocessErrorKind::UnclosedRepeat, line_no)),
+        None => unreachable!("栈底的顶层不会被弹出"),
+    }
+}
+
+impl<'a> Expander<'a> {
+    /// 构造函数
+    fn new(preprocessor: &'a Preprocessor) -> Self {
+        Self {
+            preprocessor,
+            variables: HashMap::new(),
+            file_stack: vec![],
+            result: vec![],
+        }
+    }
+
+    /// 当前文件中的位置
+    fn loc(&self, line_no: usize) -> SourceLoc {
+        SourceLoc {
+            file: self.file_stack.last().cloned().unwrap_or_default(),
+            line_no,
+        }
+    }
+
+    /// 构造错误
+    fn error(&self, kind: PreprocessErrorKind, line_no: usize) -> PreprocessError {
+        PreprocessError {
+            kind,
+            source: self.loc(line_no),
+        }
+    }
+
+    /// 替换其中的`${变量名}`
+    fn substitute(&self, text: &str, line_no: usize) -> PreprocessResult<String> {
+        let mut result = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find("${") {
+            result.push_str(&rest[..start]);
+            let after = &rest[start + 2..];
+            // 无右括号⇒原样保留
+            let Some(end) = after.find('}') else {
+                result.push_str(&rest[start..]);
+                return Ok(result);
+            };
+            let name = &after[..end];
+            match self.variables.get(name) {
+                Some(value) => result.push_str(value),
+                None => {
+                    let kind = PreprocessErrorKind::UndefinedVariable(name.to_owned());
+                    return Err(self.error(kind, line_no));
+                }
+            }
+            rest = &after[end + 1..];
+        }
+        result.push_str(rest);
+        Ok(result)
+    }
+
+    /// 读取并展开文件
+    /// * 📌`from`：引入此文件的位置（顶层为[`None`]）
+    fn expand_file(&mut self, path: &Path, from: Option<usize>) -> PreprocessResult<()> {
+        let path = &normalize_path(path);
+        let src = (self.preprocessor.loader)(path).map_err(|e| {
+            let kind = PreprocessErrorKind::Io {
+                path: path.to_owned(),
+                error: Arc::new(e),
+            };
+            self.error(kind, from.unwrap_or(0))
+        })?;
+        self.expand_src(&src, path)
+    }
+
+    /// 展开一个文件的内容
+    fn expand_src(&mut self, src: &str, file: &Path) -> PreprocessResult<()> {
+        let file = normalize_path(file);
+        let items = parse_items(&file, logical_lines(src))?;
+        self.file_stack.push(file);
+        self.expand_items(&items)?;
+        self.file_stack.pop();
+        Ok(())
+    }
+
+    /// 展开各项
+    fn expand_items(&mut self, items: &[Item]) -> PreprocessResult<()> {
+        for item in items {
+            match item {
+                Item::Line { line_no, text } => {
+                    let line = self.substitute(text, *line_no)?;
+                    let cmd = Cmd::parse_with_options(&line, &self.preprocessor.parse_options)
+                        .map_err(|e| self.error(PreprocessErrorKind::Parse(e), *line_no))?;
+                    let source = self.loc(*line_no);
+                    self.result.push(ExpandedCmd { cmd, source });
+                }
+                Item::Set {
+                    line_no,
+                    name,
+                    value,
+                } => {
+                    let value = self.substitute(value, *line_no)?;
+                    self.variables.insert(name.clone(), value);
+                }
+                Item::Include { line_no, path } => {
+                    let path = self.substitute(path, *line_no)?;
+                    // 相对于当前文件所在目录
+                    let current = self.file_stack.last().cloned().unwrap_or_default();
+                    let path = normalize_path(&current.parent().unwrap_or(Path::new("")).join(path));
+                    if self.file_stack.contains(&path) {
+                        return Err(self.error(PreprocessErrorKind::IncludeCycle(path), *line_no));
+                    }
+                    if self.file_stack.len() >= MAX_INCLUDE_DEPTH {
+                        let kind = PreprocessErrorKind::IncludeTooDeep(path);
+                        return Err(self.error(kind, *line_no));
+                    }
+                    self.expand_file(&path, Some(*line_no))?;
+                }
+                Item::Repeat {
+                    line_no,
+                    count,
+                    var,
+                    body,
+                } => {
+                    let count = self.substitute(count, *line_no)?;
+                    let n = count.parse::<usize>().map_err(|_| {
+                        self.error(PreprocessErrorKind::InvalidRepeatCount(count), *line_no)
+                    })?;
+                    // 序号变量：展开后恢复原值
+                    let shadowed = var.as_ref().and_then(|var| self.variables.get(var).cloned());
+                    for i in 0..n {
+                        if let Some(var) = var {
+                            self.variables.insert(var.clone(), i.to_string());
+                        }
+                        self.expand_items(body)?;
+                    }
+                    if let Some(var) = var {
+                        match shadowed {
+                            Some(value) => self.variables.insert(var.clone(), value),
+                            None => self.variables.remove(var),
+                        };
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nar_dev_utils::asserts;
+
+    /// 从内存中读取文件的预处理器
+    fn preprocessor(files: &[(&str, &str)]) -> Preprocessor {
+        let files = files
+            .iter()
+            .map(|(path, src)| (PathBuf::from(path), src.to_string()))
+            .collect::<HashMap<_, _>>();
+        Preprocessor::with_loader(move |path| {
+            files
+                .get(path)
+                .cloned()
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "not found"))
+        })
+    }
+
+    #[test]
+    fn test_preprocess() {
+        let preprocessor = preprocessor(&[
+            (
+                "tests/main.navm",
+                "@include common/background.navm
+                @set steps 10
+                @repeat 2 i
+                    NSE <${subject}${i} --> B>.
+                    CYC ${steps}
+                @end
+                NSE <$x --> ${subject}>?",
+            ),
+            (
+                "tests/common/background.navm",
+                "// 共用的背景知识
+                @set subject A
+                NSE <B --> C>.",
+            ),
+        ]);
+        let expanded = preprocessor.expand_file("tests/main.navm").unwrap();
+        let sources = expanded
+            .iter()
+            .map(|ExpandedCmd { source, .. }| source.to_string())
+            .collect::<Vec<_>>();
+        let expected = [
+            "NSE <B --> C>.",
+            "NSE <A0 --> B>.",
+            "CYC 10",
+            "NSE <A1 --> B>.",
+            "CYC 10",
+            "NSE <$x --> A>?",
+        ]
+        .map(|line| Cmd::parse(line).unwrap());
+        asserts! {
+            into_cmds(expanded) => expected
+            sources => [
+                "tests/common/background.navm:3",
+                "tests/main.navm:4",
+                "tests/main.navm:5",
+                "tests/main.navm:4",
+                "tests/main.navm:5",
+                "tests/main.navm:7",
+            ]
+        }
+    }
+
+    #[test]
+    fn test_preprocess_errors() {
+        let preprocessor = preprocessor(&[
+            ("a.navm", "@include b.navm"),
+            ("b.navm", "CYC 1\n@include a.navm"),
+            ("c.navm", "CYC 1\n@include sub/../c.navm"),
+        ]);
+        let expand = |src: &str| preprocessor.expand_str(src, "main.navm").unwrap_err();
+        let cases = [
+            ("CYC 1\n@end", 2),
+            ("@repeat 2\nCYC 1", 1),
+            ("@repeat x\nCYC 1\n@end", 1),
+            ("CYC ${undefined}", 1),
+            ("@unknown", 1),
+            ("@include missing.navm", 1),
+            ("@repeat 2\nCYC a\n@end", 2),
+            ("@include", 1),
+        ];
+        for (src, line_no) in cases {
+            let e = expand(src);
+            println!("{e}");
+            assert_eq!(e.source.line_no, line_no, "{src:?}");
+        }
+        // 循环引入
+        let e = preprocessor.expand_file("a.navm").unwrap_err();
+        println!("{e}");
+        asserts! {
+            matches!(e.kind, PreprocessErrorKind::IncludeCycle(..)) => true
+            e.source.file => PathBuf::from("b.navm")
+        }
+        // 经`..`绕回自身的循环引入
+        let e = preprocessor.expand_file("./c.navm").unwrap_err();
+        println!("{e}");
+        asserts! {
+            matches!(e.kind, PreprocessErrorKind::IncludeCycle(..)) => true
+            e.source => SourceLoc {
+                file: PathBuf::from("c.navm"),
+                line_no: 2,
+            }
+        }
+        // 无循环但层数过深：每个文件引入下一个
+        let chain = Preprocessor::with_loader(|path| {
+            let n = path.to_string_lossy().parse::<usize>().unwrap();
+            Ok(format!("@include {}", n + 1))
+        });
+        let e = chain.expand_file("0").unwrap_err();
+        println!("{e}");
+        asserts! {
+            matches!(e.kind, PreprocessErrorKind::IncludeTooDeep(..)) => true
+            e.source.file => PathBuf::from((MAX_INCLUDE_DEPTH - 1).to_string())
+        }
+    }
+
+    #[test]
+    fn test_normalize_path() {
+        for (path, expected) in [
+            ("sub/../a.navm", "a.navm"),
+            ("./a/./b.navm", "a/b.navm"),
+            ("a/b/../../c.navm", "c.navm"),
+            ("../a.navm", "../a.navm"),
+            ("../../a/../b.navm", "../../b.navm"),
+            ("/../a.navm", "/a.navm"),
+        ] {
+            assert_eq!(normalize_path(Path::new(path)), PathBuf::from(expected), "{path}");
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! 提供「JSON化的NAVM指令」的JSON Schema
+//! * 🎯与[「NAVM输出」的Schema](crate::output::OutputJSON::json_schema)对称
+//! * 📌Schema描述的是NAVM**产生**的JSON：指令头为全大写
+//!
+//! [`CmdJSON`]: super::CmdJSON
+
+use super::{CmdJSON, BUILTIN_HEADS};
+use crate::output::{string_with_examples, vec_schema, JSON_SCHEMA_DIALECT};
+use serde_json::{json, Value};
+
+/// 「指令头→所需字段」的条件
+/// * 🚩生成`if head == ... then required [...]`
+fn require_for_head(head: &str, fields: &[&str]) -> Value {
+    json!({
+        "if": {
+            "properties": { "head": { "const": head } },
+            "required": ["head"],
+        },
+        "then": { "required": fields },
+    })
+}
+
+impl CmdJSON {
+    /// 生成[`CmdJSON`]的JSON Schema
+    pub fn json_schema() -> Value {
+        /// 字符串字段
+        fn string(description: &str) -> Value {
+            json!({ "description": description, "type": "string" })
+        }
+        json!({
+            "$schema": JSON_SCHEMA_DIALECT,
+            "title": "CmdJSON",
+            "description": "NAVM指令",
+            "type": "object",
+            "properties": {
+                "head": string_with_examples(&BUILTIN_HEADS, "指令头（全大写）"),
+                "target": string("SAV/LOA/RES/NEW/DEL：目标"),
+                "path": string("SAV/LOA：路径"),
+                "narsese": string("NSE：ASCII CommonNarsese字符串"),
+                "num": {
+                    "description": "CYC/VOL：数值",
+                    "type": "integer",
+                    "minimum": 0,
+                },
+                "name": string("REG/HLP：名称"),
+                "source": string("INF：来源"),
+                "comment": string("REM：注释"),
+                "reason": string("EXI：原因"),
+                "tail": string("自定义指令：指令尾"),
+            },
+            "required": ["head"],
+            "additionalProperties": false,
+            "allOf": [
+                require_for_head("NSE", &["narsese"]),
+                require_for_head("CYC", &["num"]),
+                require_for_head("VOL", &["num"]),
+            ],
+        })
+    }
+
+    /// 生成「[`CmdJSON`]数组」的JSON Schema
+    /// * 🎯对应[`Cmd::vec_to_json_string`](super::Cmd::vec_to_json_string)
+    pub fn vec_json_schema() -> Value {
+        let mut item_schema = Self::json_schema();
+        item_schema.as_object_mut().unwrap().remove("$schema");
+        vec_schema("CmdJSON", item_schema)
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cmd::{structs::tests::samples, Cmd},
+        output::schema_validator,
+    };
+
+    #[test]
+    fn test_json_schema() {
+        let schema = CmdJSON::json_schema();
+        let validator = schema_validator(&schema);
+        let samples = samples();
+        for cmd in &samples {
+            let value = serde_json::to_value(cmd.to_json_struct()).unwrap();
+            if let Err(e) = validator.validate(&value) {
+                panic!("样本未通过校验：{e}");
+            }
+        }
+        let value = serde_json::from_str(&Cmd::vec_to_json_string(&samples)).unwrap();
+        assert!(schema_validator(&CmdJSON::vec_json_schema()).is_valid(&value));
+        // 自定义指令头亦合法
+        assert!(validator.is_valid(&json!({ "head": "CUSTOM", "tail": "" })));
+        for invalid in [
+            json!({ "narsese": "<A --> B>." }),
+            json!({ "head": "NSE" }),
+            json!({ "head": "CYC", "num": -1 }),
+            json!({ "head": "REM", "extra": "" }),
+        ] {
+            assert!(!validator.is_valid(&invalid), "{invalid}");
+        }
+    }
+}
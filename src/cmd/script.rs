@@ -0,0 +1,271 @@
+//! 定义「NAVM脚本」的多行解析器
+//! * 🎯一次性解析整个`.navm`文件，而非逐行调用[`Cmd::parse`]
+//! * 📌脚本语法（在单行指令语法之上）
+//!   * 空行：忽略
+//!   * 注释：`//`开头的整行，或行内位于空白符之后的`//`及其后内容
+//!     * 📌引号括起的参数中的`//`不算注释，如`SAV memory "a // b"`
+//!     * ⚠️不同于[`Cmd::REM`]：脚本注释不会产生任何指令
+//!     * ⚠️不使用`#`：其在Narsese中表示「独立变量」
+//!   * 续行：行尾的`\`表示「下一行接续本行」
+//!     * 🎯用于书写较长的`NSE`Narsese
+//!     * 📌`\`须单独成词：其前为空白符（或整行仅有`\`）
+//!       * 📄`NSE <A --> B>. :\:`中的`\`不是续行标记
+//!     * 🚩续行之间以单个空格拼接
+//! * 📌行号从1开始；续行的行号取其首行
+
+use super::{Cmd, ParseError, ParseOptions, PARAM_ESCAPE, PARAM_QUOTE};
+
+/// 注释标记
+pub const SCRIPT_COMMENT: &str = "//";
+
+/// 续行标记
+pub const SCRIPT_CONTINUATION: char = '\\';
+
+/// 脚本中的一条指令
+/// * 🚩指令+所在行号
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptCmd {
+    /// 所在行号（从1开始）
+    pub line_no: usize,
+    /// 解析出的指令
+    pub cmd: Cmd,
+}
+
+/// 脚本中的一处解析错误
+/// * 🚩错误+所在行号
+/// * 📌其中错误的出错范围，相对于（拼接续行、去除注释后的）该行内容
+#[derive(Debug, Clone)]
+pub struct ScriptError {
+    /// 所在行号（从1开始）
+    pub line_no: usize,
+    /// 该行的解析错误
+    pub error: ParseError,
+}
+
+impl std::fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line_no, self.error)
+    }
+}
+
+impl std::error::Error for ScriptError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// NAVM脚本
+/// * 🎯存储整个脚本的解析结果
+/// * 📌不会在首个错误处停止：所有错误均被收集
+#[derive(Debug, Clone, Default)]
+pub struct CmdScript {
+    /// 成功解析的指令（按出现顺序）
+    pub cmds: Vec<ScriptCmd>,
+    /// 所有解析错误（按出现顺序）
+    pub errors: Vec<ScriptError>,
+}
+
+impl CmdScript {
+    /// 从整个脚本文本解析
+    pub fn parse(src: &str) -> Self {
+        Self::parse_with_options(src, &ParseOptions::default())
+    }
+
+    /// 按解析选项，从整个脚本文本解析
+    /// * 🔗逐行使用[`Cmd::parse_with_options`]
+    pub fn parse_with_options(src: &str, options: &ParseOptions) -> Self {
+        let mut script = Self::default();
+        for (line_no, line) in logical_lines(src) {
+            match Cmd::parse_with_options(&line, options) {
+                Ok(cmd) => script.cmds.push(ScriptCmd { line_no, cmd }),
+                Err(error) => script.errors.push(ScriptError { line_no, error }),
+            }
+        }
+        script
+    }
+
+    /// 是否无任何错误
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// 遍历其中的指令（不带行号）
+    pub fn iter_cmds(&self) -> impl Iterator<Item = &Cmd> {
+        self.cmds.iter().map(|ScriptCmd { cmd, .. }| cmd)
+    }
+
+    /// 转换为[`Result`]
+    /// * 🚩有错误⇒返回所有错误；否则返回所有指令
+    pub fn into_result(self) -> Result<Vec<ScriptCmd>, Vec<ScriptError>> {
+        match self.errors.is_empty() {
+            true => Ok(self.cmds),
+            false => Err(self.errors),
+        }
+    }
+}
+
+/// 从整个脚本文本解析
+/// * 🔗[`CmdScript::parse`]的快捷方式
+#[inline]
+pub fn parse_script(src: &str) -> CmdScript {
+    CmdScript::parse(src)
+}
+
+/// 去掉一行中的注释
+/// * 🚩整行注释⇒空；行内注释需位于空白符之后
+/// * 🚩跳过引号括起的参数：引号与转义规则同指令参数
+///   * 📌仅在参数开头识别引号；未闭合⇒直到行尾
+pub(crate) fn strip_comment(line: &str) -> &str {
+    if line.trim_start().starts_with(SCRIPT_COMMENT) {
+        return "";
+    }
+    let mut chars = line.char_indices();
+    // 前一个字符是否为空白符
+    let mut after_whitespace = false;
+    while let Some((i, c)) = chars.next() {
+        match c {
+            // 引号括起的参数：跳到闭合的引号之后
+            PARAM_QUOTE if after_whitespace => {
+                while let Some((_, c)) = chars.next() {
+                    match c {
+                        PARAM_QUOTE => break,
+                        PARAM_ESCAPE => {
+                            chars.next();
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            // 仅在前一个字符为空白符时视作注释
+            _ if after_whitespace && line[i..].starts_with(SCRIPT_COMMENT) => return &line[..i],
+            _ => {}
+        }
+        after_whitespace = c.is_whitespace();
+    }
+    line
+}
+
+/// 将脚本文本拆分为「逻辑行」
+/// * 🚩去除注释、跳过空行、拼接续行
+/// * 🚩续行标记须位于行尾，且其前为空白符
+/// * 📌返回（首行行号，去除首尾空白的行内容）
+pub(crate) fn logical_lines(src: &str) -> Vec<(usize, String)> {
+    let mut result = vec![];
+    // 正在拼接的续行：（首行行号，已拼接内容）
+    let mut pending: Option<(usize, String)> = None;
+    for (i, raw_line) in src.lines().enumerate() {
+        let line_no = i + 1;
+        let line = strip_comment(raw_line).trim();
+        // 检查续行标记：须单独成词
+        let (content, continued) = match line.strip_suffix(SCRIPT_CONTINUATION) {
+            Some(content) if content.is_empty() || content.ends_with(char::is_whitespace) => {
+                (content.trim_end(), true)
+            }
+            _ => (line, false),
+        };
+        // 拼接到已有内容
+        let (first_line_no, mut joined) = pending.take().unwrap_or((line_no, String::new()));
+        if !joined.is_empty() && !content.is_empty() {
+            joined.push(' ');
+        }
+        joined.push_str(content);
+        match continued {
+            true => pending = Some((first_line_no, joined)),
+            // 空行直接跳过
+            false if joined.is_empty() => {}
+            false => result.push((first_line_no, joined)),
+        }
+    }
+    // 末尾未结束的续行：照常收录
+    if let Some((line_no, joined)) = pending {
+        if !joined.is_empty() {
+            result.push((line_no, joined));
+        }
+    }
+    result
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nar_dev_utils::asserts;
+
+    /// 测试/脚本解析
+    /// * 🎯空行、注释、续行、行号
+    #[test]
+    fn test_parse_script() {
+        let script = parse_script(
+            "// 整行注释
+            NSE <A --> B>. // 行内注释
+
+            NSE <(&&, <#x --> B>, \\
+                 <#x --> C>) --> D>.
+            CYC 10
+            SAV memory http://example.com/a.json
+            SAV memory \"a // b\" // 引号内不算注释
+            LOA memory \"a \\\" // b\" // 引号内的转义
+            ",
+        );
+        assert!(script.is_ok());
+        let line_nos = script.cmds.iter().map(|c| c.line_no).collect::<Vec<_>>();
+        asserts! {
+            line_nos => [2, 4, 6, 7, 8, 9]
+            script.cmds[2].cmd => Cmd::CYC(10)
+            script.cmds[3].cmd => Cmd::SAV {
+                target: "memory".into(),
+                path: "http://example.com/a.json".into(),
+            }
+            script.cmds[4].cmd => Cmd::SAV {
+                target: "memory".into(),
+                path: "a // b".into(),
+            }
+            script.cmds[5].cmd => Cmd::LOA {
+                target: "memory".into(),
+                path: "a \" // b".into(),
+            }
+            strip_comment("REM a\"b // c") => "REM a\"b "
+            strip_comment(r#"REM "unterminated // c"#) => r#"REM "unterminated // c"#
+        }
+    }
+
+    /// 测试/错误收集
+    /// * 🎯不在首个错误处停止
+    #[test]
+    fn test_collect_errors() {
+        let script = parse_script(
+            "CYC a
+            CYC 1
+            NEW
+            NSE <A --> B",
+        );
+        let error_line_nos = script.errors.iter().map(|e| e.line_no).collect::<Vec<_>>();
+        asserts! {
+            script.cmds.len() => 1
+            error_line_nos => [1, 3, 4]
+        }
+        for error in &script.errors {
+            println!("{error}");
+        }
+        assert!(script.into_result().is_err());
+    }
+    /// 测试/续行标记
+    /// * 🎯行尾的`\`须单独成词，才是续行标记
+    #[test]
+    fn test_continuation() {
+        asserts! {
+            logical_lines("NSE <A --> B>. :\\:\nCYC 1") => vec![
+                (1, "NSE <A --> B>. :\\:".to_owned()),
+                (2, "CYC 1".to_owned()),
+            ]
+            logical_lines("REM a\\\nCYC 1") => vec![
+                (1, "REM a\\".to_owned()),
+                (2, "CYC 1".to_owned()),
+            ]
+            logical_lines("REM a \\\n\\\nb") => vec![(1, "REM a b".to_owned())]
+        }
+        let script = parse_script("NSE <A --> B>. :\\:\nCYC 1");
+        assert!(script.is_ok());
+        assert_eq!(script.cmds[0].cmd.to_string(), "NSE <A --> B>. :\\:");
+    }
+}
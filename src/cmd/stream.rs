@@ -0,0 +1,287 @@
+//! 提供「NAVM指令」的流式读写
+//! * 🎯长时间运行的管道：逐行读取指令、逐行写出指令，而无需一次性载入全部内容
+//!   * 📄stdin/stdout桥接、大型指令文件回放
+//! * 📌读取：基于[`Cmd::parse`]；写出：基于[`Cmd`]的格式化
+//!   * ⚠️不同于[「NAVM脚本」](super::CmdScript)：不处理注释、续行
+
+use super::{Cmd, FormatOptions, ParseError, ParseErrorKind, ParseOptions, ParseResult};
+use std::{
+    io::{self, BufRead, Write},
+    sync::Arc,
+};
+
+/// UTF-8的字节顺序标记
+const UTF8_BOM: &str = "\u{feff}";
+
+/// 默认的单行长度上限（字节）
+pub const DEFAULT_MAX_LINE_LEN: usize = 1 << 20;
+
+/// 指令读取器
+/// * 🚩逐行读取并解析，产生（行号，解析结果）
+///   * 📌行号从1开始
+///   * 📌空行（仅含空白符）将被跳过
+/// * ✨容错：单行出错不影响后续行
+///   * 📌兼容CRLF换行、首行的UTF-8 BOM
+///   * 📌过长的行将被跳过（不会整行载入内存），并产生[`ParseErrorKind::LineTooLong`]
+///   * 📌读取错误将产生[`ParseErrorKind::Io`]，并结束迭代
+#[derive(Debug)]
+pub struct CmdReader<R: BufRead> {
+    /// 内部读取器
+    reader: R,
+    /// 解析选项
+    options: ParseOptions,
+    /// 单行长度上限（字节，不含换行符）
+    max_line_len: usize,
+    /// 已读取的行数
+    line_no: usize,
+    /// 行缓冲区
+    buf: Vec<u8>,
+    /// 是否已结束（读到末尾/读取出错）
+    finished: bool,
+}
+
+impl<R: BufRead> CmdReader<R> {
+    /// 构造函数
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            options: ParseOptions::default(),
+            max_line_len: DEFAULT_MAX_LINE_LEN,
+            line_no: 0,
+            buf: vec![],
+            finished: false,
+        }
+    }
+
+    /// 设置解析选项
+    pub fn with_options(mut self, options: ParseOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// 设置单行长度上限（字节）
+    pub fn with_max_line_len(mut self, max_line_len: usize) -> Self {
+        self.max_line_len = max_line_len;
+        self
+    }
+
+    /// 取回内部读取器
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// 读取一行（不含换行符）到缓冲区
+    /// * 🚩超出上限的部分直接丢弃，返回「是否过长」
+    /// * 📌读到末尾且无内容⇒[`None`]
+    fn read_line(&mut self) -> io::Result<Option<bool>> {
+        self.buf.clear();
+        let mut too_long = false;
+        let mut read_any = false;
+        loop {
+            let available = match self.reader.fill_buf() {
+                Ok(available) => available,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            // 读到末尾
+            if available.is_empty() {
+                return Ok(read_any.then_some(too_long));
+            }
+            read_any = true;
+            // 寻找换行符
+            let (chunk, found_newline) = match available.iter().position(|&b| b == b'\n') {
+                Some(i) => (&available[..i], true),
+                None => (available, false),
+            };
+            // 仅在未超出上限时存入缓冲区
+            if !too_long {
+                match self.buf.len() + chunk.len() > self.max_line_len {
+                    true => {
+                        too_long = true;
+                        self.buf.clear();
+                    }
+                    false => self.buf.extend_from_slice(chunk),
+                }
+            }
+            let consumed = chunk.len() + found_newline as usize;
+            self.reader.consume(consumed);
+            if found_newline {
+                return Ok(Some(too_long));
+            }
+        }
+    }
+
+    /// 将缓冲区中的行解析为指令
+    /// * 🚩空行⇒[`None`]
+    fn parse_buf(&self) -> Option<ParseResult<Cmd>> {
+        let line = match std::str::from_utf8(&self.buf) {
+            Ok(line) => line,
+            Err(e) => {
+                let start = e.valid_up_to();
+                let end = start + e.error_len().unwrap_or(self.buf.len() - start);
+                return Some(Err(ParseError::new(ParseErrorKind::InvalidUtf8, start..end)));
+            }
+        };
+        // 去掉CRLF中的CR
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        // 去掉首行的BOM
+        let line = match self.line_no {
+            1 => line.strip_prefix(UTF8_BOM).unwrap_or(line),
+            _ => line,
+        };
+        match line.trim().is_empty() {
+            true => None,
+            false => Some(Cmd::parse_with_options(line, &self.options)),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for CmdReader<R> {
+    type Item = (usize, ParseResult<Cmd>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.finished {
+            let read = self.read_line();
+            match read {
+                // 读到末尾
+                Ok(None) => self.finished = true,
+                // 过长的行
+                Ok(Some(true)) => {
+                    self.line_no += 1;
+                    let kind = ParseErrorKind::LineTooLong {
+                        max: self.max_line_len,
+                    };
+                    let error = ParseError::new(kind, 0..self.max_line_len);
+                    return Some((self.line_no, Err(error)));
+                }
+                // 正常的行：跳过空行
+                Ok(Some(false)) => {
+                    self.line_no += 1;
+                    if let Some(result) = self.parse_buf() {
+                        return Some((self.line_no, result));
+                    }
+                }
+                // 读取错误：结束迭代
+                Err(e) => {
+                    self.finished = true;
+                    let error = ParseError::new(ParseErrorKind::Io(Arc::new(e)), 0..0);
+                    return Some((self.line_no + 1, Err(error)));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// 指令写出器
+/// * 🚩每条指令格式化后单独成行（以`\n`结尾）
+/// * ⚠️格式化结果含换行符（如多行注释）的指令无法单独成行，写出时报错
+#[derive(Debug)]
+pub struct CmdWriter<W: Write> {
+    /// 内部写出器
+    writer: W,
+    /// 格式化选项
+    options: FormatOptions,
+}
+
+impl<W: Write> CmdWriter<W> {
+    /// 构造函数
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            options: FormatOptions::default(),
+        }
+    }
+
+    /// 设置格式化选项
+    pub fn with_options(mut self, options: FormatOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// 写出一条指令
+    pub fn write_cmd(&mut self, cmd: &Cmd) -> io::Result<()> {
+        let line = cmd.format_with(&self.options);
+        if line.contains(['\n', '\r']) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("指令无法单独成行：{line:?}"),
+            ));
+        }
+        writeln!(self.writer, "{line}")
+    }
+
+    /// 写出多条指令
+    pub fn write_cmds<'a>(&mut self, cmds: impl IntoIterator<Item = &'a Cmd>) -> io::Result<()> {
+        cmds.into_iter().try_for_each(|cmd| self.write_cmd(cmd))
+    }
+
+    /// 刷新内部写出器
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// 取回内部写出器
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmd::structs::tests::samples;
+    use nar_dev_utils::asserts;
+
+    /// 测试/读取
+    /// * 🎯CRLF、BOM、空行、错误恢复
+    #[test]
+    fn test_reader() {
+        let src = "\u{feff}CYC 1\r\n\r\nVOL a\r\nNSE <A --> B>.\nRES";
+        let results = CmdReader::new(src.as_bytes()).collect::<Vec<_>>();
+        let line_nos = results.iter().map(|(line_no, _)| *line_no).collect::<Vec<_>>();
+        asserts! {
+            line_nos => [1, 3, 4, 5]
+            results[0].1.as_ref().unwrap() => &Cmd::CYC(1)
+            results[1].1.is_err() => true
+            results[2].1.as_ref().unwrap() => &Cmd::parse("NSE <A --> B>.").unwrap()
+            results[3].1.as_ref().unwrap() => &Cmd::parse("RES").unwrap()
+        }
+    }
+
+    /// 测试/读取/过长的行与非法UTF-8
+    #[test]
+    fn test_reader_errors() {
+        let long_line = format!("REM {}", "a".repeat(100));
+        let src = [long_line.as_bytes(), b"\nCYC 2\nREM \xff\xfe\nCYC 3\n"].concat();
+        let results = CmdReader::new(&src[..])
+            .with_max_line_len(50)
+            .collect::<Vec<_>>();
+        assert_eq!(results.len(), 4);
+        let kind = |i: usize| results[i].1.as_ref().unwrap_err().kind().clone();
+        assert!(matches!(kind(0), ParseErrorKind::LineTooLong { max: 50 }));
+        assert!(matches!(kind(2), ParseErrorKind::InvalidUtf8));
+        asserts! {
+            results[1].1.as_ref().unwrap() => &Cmd::CYC(2)
+            results[3].0 => 4
+            results[3].1.as_ref().unwrap() => &Cmd::CYC(3)
+        }
+    }
+
+    /// 测试/写出→读取 无损
+    #[test]
+    fn test_writer() {
+        let samples = samples();
+        let mut writer = CmdWriter::new(vec![]);
+        writer.write_cmds(&samples).unwrap();
+        let written = writer.into_inner();
+        let read = CmdReader::new(&written[..])
+            .map(|(_, result)| result.unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(read, samples);
+        // 多行内容无法写出
+        let mut writer = CmdWriter::new(vec![]);
+        assert!(writer.write_cmd(&Cmd::rem("a\nb")).is_err());
+    }
+}
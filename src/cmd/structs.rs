@@ -3,6 +3,7 @@
 //!   * 【2024-03-22 17:34:48】⚠️也有可能是一种限制
 //! * 🚩【2024-03-23 00:18:57】目前这里只定义结构，不定义其实现
 
+use super::{ExtCmd, Target};
 use narsese::lexical::Task as LexicalTask;
 
 /// NAVM指令 数据结构
@@ -10,16 +11,16 @@ use narsese::lexical::Task as LexicalTask;
 pub enum Cmd {
     /// `Save` | 在path保存target
     /// 指令：保存当前数据（记忆）到文件
-    SAV { target: String, path: String },
+    SAV { target: Target, path: String },
 
     /// `Load` | 从path加载target
     /// 指令：从文件加载数据（记忆）
-    LOA { target: String, path: String },
+    LOA { target: Target, path: String },
 
     /// `Reset` | 重置target
     /// 指令：重置CIN数据
     /// * 如：记忆区、缓冲区……
-    RES { target: String },
+    RES { target: Target },
 
     /// 指令：输入「CommonNarsese」语句
     /// * 🚩使用「词法Narsese」（[`narsese::lexical`]）作为数据结构
@@ -51,7 +52,7 @@ pub enum Cmd {
 
     /// `Info`
     /// 指令：让CIN输出某类信息
-    INF { source: String },
+    INF { source: Target },
 
     /// `Help` | 帮助
     /// 指令：打印（CIN的）帮助文档
@@ -83,6 +84,18 @@ pub enum Cmd {
         /// 指令头以外的「指令尾」部分
         tail: String,
     },
+
+    /// `Extension`
+    /// 指令：扩展
+    ///
+    /// * 🎯由[`CmdRegistry`]注册、解析的「强类型自定义指令」
+    ///   * 📄如ONA的`*motorbabbling`、`*setopname`
+    /// * 🚩【2026-10-17 04:10:22】在[`Cmd::Custom`]之外，另以「特征对象」的形式提供
+    ///   * 📌格式化、比对均以「指令头+指令尾」进行，与[`Cmd::Custom`]同形
+    ///   * ⚠️不经注册表解析时，同样的文本将被解析为[`Cmd::Custom`]
+    ///
+    /// [`CmdRegistry`]: super::CmdRegistry
+    Ext(ExtCmd),
 }
 
 /// 单元测试
@@ -0,0 +1,119 @@
+//! 定义指令[`Cmd::SAV`] [`Cmd::LOA`] [`Cmd::RES`] [`Cmd::INF`]所用的「目标」
+//! * 🎯统一「记忆区」「缓冲区」等常用目标的写法，免得各CIN转译器各自猜测
+//! * 📌文本语法不变：仍是单个字符串参数
+//!   * 🚩已知名称（全小写）⇒对应变体；其它⇒[`Target::Custom`]
+//!   * ⚠️不应手动构造名称与已知目标相同的[`Target::Custom`]：重解析后将不相等
+//!
+//! [`Cmd::SAV`]: super::Cmd::SAV
+//! [`Cmd::LOA`]: super::Cmd::LOA
+//! [`Cmd::RES`]: super::Cmd::RES
+//! [`Cmd::INF`]: super::Cmd::INF
+
+use std::fmt::Display;
+
+/// 指令目标
+/// * 📌缺省（空字串）的目标⇒空的[`Target::Custom`]，其含义由CIN自行决定
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Target {
+    /// 记忆区 | `memory`
+    Memory,
+    /// 缓冲区 | `buffer`
+    Buffer,
+    /// 概念 | `concepts`
+    Concepts,
+    /// 任务 | `tasks`
+    Tasks,
+    /// 操作符 | `operators`
+    Operators,
+    /// 全部 | `all`
+    All,
+    /// 自定义目标
+    /// * 🎯后续一切「CIN特定目标」的后门
+    Custom(String),
+}
+
+impl Target {
+    /// 所有已知目标（不含[`Target::Custom`]）
+    pub const KNOWN: [Target; 6] = [
+        Target::Memory,
+        Target::Buffer,
+        Target::Concepts,
+        Target::Tasks,
+        Target::Operators,
+        Target::All,
+    ];
+
+    /// 获取目标名称
+    /// * 🚩与文本语法中的写法一致
+    pub fn as_str(&self) -> &str {
+        match self {
+            Target::Memory => "memory",
+            Target::Buffer => "buffer",
+            Target::Concepts => "concepts",
+            Target::Tasks => "tasks",
+            Target::Operators => "operators",
+            Target::All => "all",
+            Target::Custom(name) => name,
+        }
+    }
+
+    /// 是否为缺省目标（空字串）
+    pub fn is_empty(&self) -> bool {
+        self.as_str().is_empty()
+    }
+}
+
+/// 缺省目标：空字串
+impl Default for Target {
+    fn default() -> Self {
+        Target::Custom(String::new())
+    }
+}
+
+impl From<String> for Target {
+    fn from(name: String) -> Self {
+        match name.as_str() {
+            "memory" => Target::Memory,
+            "buffer" => Target::Buffer,
+            "concepts" => Target::Concepts,
+            "tasks" => Target::Tasks,
+            "operators" => Target::Operators,
+            "all" => Target::All,
+            _ => Target::Custom(name),
+        }
+    }
+}
+
+impl From<&str> for Target {
+    fn from(name: &str) -> Self {
+        Self::from(name.to_owned())
+    }
+}
+
+impl Display for Target {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nar_dev_utils::asserts;
+
+    #[test]
+    fn test_target() {
+        // 已知目标：名称↔变体
+        for target in Target::KNOWN {
+            assert_eq!(Target::from(target.as_str()), target);
+        }
+        asserts! {
+            Target::from("memory") => Target::Memory
+            Target::from("Memory") => Target::Custom("Memory".into())
+            Target::from("") => Target::default()
+            Target::default().is_empty() => true
+            Target::Operators.to_string() => "operators"
+        }
+    }
+}
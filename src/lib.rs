@@ -8,3 +8,9 @@ pub mod output;
 
 // NAVM虚拟机
 pub mod vm;
+
+// 转译器
+pub mod translators;
+
+// 工具函数
+mod util;
@@ -190,6 +190,18 @@ impl Output {
             },
             INFO => Output::INFO { message: content },
             COMMENT => Output::COMMENT { content },
+            ANTICIPATE => Output::ANTICIPATE {
+                content_raw: content,
+                narsese,
+            },
+            CONFIRM => Output::CONFIRM {
+                content_raw: content,
+                narsese,
+            },
+            DISAPPOINT => Output::DISAPPOINT {
+                content_raw: content,
+                narsese,
+            },
             TERMINATED => Output::TERMINATED {
                 description: content,
             },
@@ -0,0 +1,268 @@
+//! 定义「NAVM输出」与词法Narsese的语义等价比较
+//! * 🎯比较输出时忽略「写法不同、含义相同」的差异
+//!   * 📄`<(&&,A,B) --> C>`与`<(&&,B,A) --> C>`：可交换的复合词项
+//!   * 📄`<A <-> B>`与`<B <-> A>`：对称的系词
+//!   * 📄`%1.0;0.9%`与`%1.00;0.90%`：数值相同、写法不同
+//! * 📌[`Output`]派生的[`PartialEq`]仍为结构比较；此处为额外的比较方式
+//! * 🚩先将词项规范化，再结构比较；真值、预算值按数值在容差内比较
+
+use super::Output;
+use narsese::lexical::{
+    Narsese as LexicalNarsese, Sentence as LexicalSentence, Term as LexicalTerm,
+};
+
+/// 可交换的连接词
+/// * 📌组分顺序无关
+const COMMUTATIVE_CONNECTERS: &[&str] = &["&", "|", "&&", "||", "&|"];
+
+/// 对称的系词
+/// * 📌主谓项顺序无关
+const SYMMETRIC_COPULAS: &[&str] = &["<->", "<=>", "<|>"];
+
+/// 默认容差
+pub const DEFAULT_EPSILON: f64 = 1e-6;
+
+/// 比较容差
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance {
+    /// 真值各分量的容差
+    pub truth: f64,
+    /// 预算值各分量的容差
+    pub budget: f64,
+    /// 是否忽略原始内容
+    /// * 🎯不同CIN对同一输出的原始文本往往不同
+    pub ignore_content_raw: bool,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self {
+            truth: DEFAULT_EPSILON,
+            budget: DEFAULT_EPSILON,
+            ignore_content_raw: false,
+        }
+    }
+}
+
+impl Tolerance {
+    /// 以统一的容差构造
+    pub fn new(epsilon: f64) -> Self {
+        Self {
+            truth: epsilon,
+            budget: epsilon,
+            ..Default::default()
+        }
+    }
+
+    /// 设置是否忽略原始内容
+    pub fn ignore_content_raw(mut self, ignore: bool) -> Self {
+        self.ignore_content_raw = ignore;
+        self
+    }
+}
+
+/// 规范化词项
+/// * 🚩可交换的复合词项、词项集：组分排序（词项集还会去重）
+/// * 🚩对称的陈述：主谓项排序
+pub fn normalize_term(term: &LexicalTerm) -> LexicalTerm {
+    use LexicalTerm::*;
+    let normalize_terms =
+        |terms: &[LexicalTerm]| terms.iter().map(normalize_term).collect::<Vec<_>>();
+    match term {
+        Atom { .. } => term.clone(),
+        Compound { connecter, terms } => {
+            let mut terms = normalize_terms(terms);
+            if COMMUTATIVE_CONNECTERS.contains(&connecter.as_str()) {
+                terms.sort();
+            }
+            Compound {
+                connecter: connecter.clone(),
+                terms,
+            }
+        }
+        Set {
+            left_bracket,
+            terms,
+            right_bracket,
+        } => {
+            let mut terms = normalize_terms(terms);
+            terms.sort();
+            terms.dedup();
+            Set {
+                left_bracket: left_bracket.clone(),
+                terms,
+                right_bracket: right_bracket.clone(),
+            }
+        }
+        Statement {
+            copula,
+            subject,
+            predicate,
+        } => {
+            let (mut subject, mut predicate) = (normalize_term(subject), normalize_term(predicate));
+            if SYMMETRIC_COPULAS.contains(&copula.as_str()) && subject > predicate {
+                std::mem::swap(&mut subject, &mut predicate);
+            }
+            Statement {
+                copula: copula.clone(),
+                subject: Box::new(subject),
+                predicate: Box::new(predicate),
+            }
+        }
+    }
+}
+
+/// 判断两个词项是否语义等价
+pub fn equivalent_terms(a: &LexicalTerm, b: &LexicalTerm) -> bool {
+    normalize_term(a) == normalize_term(b)
+}
+
+/// 按数值比较两组数值字串
+/// * 🚩个数相同、各分量之差不超过容差
+/// * 🚩存在非数值⇒按字符串比较
+fn equivalent_values(a: &[String], b: &[String], epsilon: f64) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).all(
+        |(a, b)| match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+            (Ok(a), Ok(b)) => (a - b).abs() <= epsilon,
+            _ => a.trim() == b.trim(),
+        },
+    )
+}
+
+/// 判断两个语句是否语义等价
+fn equivalent_sentences(a: &LexicalSentence, b: &LexicalSentence, tolerance: &Tolerance) -> bool {
+    a.punctuation == b.punctuation
+        && a.stamp.trim() == b.stamp.trim()
+        && equivalent_values(&a.truth, &b.truth, tolerance.truth)
+        && equivalent_terms(&a.term, &b.term)
+}
+
+/// 判断两个词法Narsese是否语义等价
+/// * 📌须同为词项/语句/任务
+pub fn equivalent_narsese(a: &LexicalNarsese, b: &LexicalNarsese, tolerance: &Tolerance) -> bool {
+    match (a, b) {
+        (LexicalNarsese::Term(a), LexicalNarsese::Term(b)) => equivalent_terms(a, b),
+        (LexicalNarsese::Sentence(a), LexicalNarsese::Sentence(b)) => {
+            equivalent_sentences(a, b, tolerance)
+        }
+        (LexicalNarsese::Task(a), LexicalNarsese::Task(b)) => {
+            equivalent_values(&a.budget, &b.budget, tolerance.budget)
+                && equivalent_sentences(&a.sentence, &b.sentence, tolerance)
+        }
+        _ => false,
+    }
+}
+
+/// 判断两个NAVM输出是否语义等价
+/// * 🚩比较类型、原始内容（可忽略）、Narsese、操作
+pub fn equivalent(a: &Output, b: &Output, tolerance: &Tolerance) -> bool {
+    if a.type_name() != b.type_name() {
+        return false;
+    }
+    if !tolerance.ignore_content_raw && a.raw_content() != b.raw_content() {
+        return false;
+    }
+    let narsese_equivalent = match (a.get_narsese(), b.get_narsese()) {
+        (Some(a), Some(b)) => equivalent_narsese(a, b, tolerance),
+        (None, None) => true,
+        _ => false,
+    };
+    let operation_equivalent = match (a.get_operation(), b.get_operation()) {
+        (Some(a), Some(b)) => {
+            a.operator_name == b.operator_name
+                && a.params.len() == b.params.len()
+                && a.params
+                    .iter()
+                    .zip(&b.params)
+                    .all(|(a, b)| equivalent_terms(a, b))
+        }
+        (None, None) => true,
+        _ => false,
+    };
+    narsese_equivalent && operation_equivalent
+}
+
+impl Output {
+    /// 判断是否与另一输出语义等价
+    /// * 🔗[`equivalent`]
+    pub fn equivalent(&self, other: &Output, tolerance: &Tolerance) -> bool {
+        equivalent(self, other, tolerance)
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::Operation;
+    use nar_dev_utils::asserts;
+    use narsese::conversion::string::impl_lexical::format_instances::FORMAT_ASCII;
+
+    /// 解析词法Narsese
+    fn nse(s: &str) -> LexicalNarsese {
+        FORMAT_ASCII.parse(s).unwrap()
+    }
+
+    #[test]
+    fn test_equivalent_narsese() {
+        let tolerance = Tolerance::default();
+        let eq = |a: &str, b: &str| equivalent_narsese(&nse(a), &nse(b), &tolerance);
+        asserts! {
+            // 可交换的复合词项、词项集
+            eq("<(&&, A, B) --> C>", "<(&&, B, A) --> C>") => true
+            eq("{A, B, A}", "{B, A}") => true
+            eq("<(*, A, B) --> C>", "<(*, B, A) --> C>") => false
+            eq("(&/, A, B)", "(&/, B, A)") => false
+            // 对称的系词
+            eq("<(|, A, B) <-> C>", "<C <-> (|, B, A)>") => true
+            eq("<A --> B>", "<B --> A>") => false
+            // 数值
+            eq("<A --> B>. %1.0;0.9%", "<A --> B>. %1.00;0.90%") => true
+            eq("$0.5;0.5;0.5$ <A --> B>.", "$0.50;0.500;0.5$ <A --> B>.") => true
+            eq("<A --> B>. %1.0;0.9%", "<A --> B>. %1.0;0.8%") => false
+            // 标点、时态、类型
+            eq("<A --> B>.", "<A --> B>?") => false
+            eq("<A --> B>. :|:", "<A --> B>.") => false
+            eq("<A --> B>", "<A --> B>.") => false
+        }
+        // 容差
+        let loose = Tolerance::new(0.1);
+        assert!(equivalent_narsese(
+            &nse("<A --> B>. %1.0;0.9%"),
+            &nse("<A --> B>. %1.0;0.85%"),
+            &loose
+        ));
+    }
+
+    #[test]
+    fn test_equivalent_output() {
+        let answer = |content_raw: &str, narsese: &str| Output::ANSWER {
+            content_raw: content_raw.into(),
+            narsese: Some(nse(narsese)),
+        };
+        let a = answer(
+            "Answer: <(&&,A,B) --> C>. %1.0;0.9%",
+            "<(&&, A, B) --> C>. %1.0;0.9%",
+        );
+        let b = answer(
+            "<(&&,B,A) --> C>. %1.00;0.90%",
+            "<(&&, B, A) --> C>. %1.00;0.90%",
+        );
+        let ignoring = Tolerance::default().ignore_content_raw(true);
+        let exe = |params: [&str; 2]| Output::EXE {
+            content_raw: String::new(),
+            operation: Operation::try_from_strings("left", params).unwrap(),
+        };
+        asserts! {
+            a == b => false
+            a.equivalent(&b, &Tolerance::default()) => false
+            a.equivalent(&b, &ignoring) => true
+            exe(["{SELF}", "(&&, x, y)"]).equivalent(&exe(["{SELF}", "(&&, y, x)"]), &ignoring) => true
+            exe(["{SELF}", "x"]).equivalent(&exe(["x", "{SELF}"]), &ignoring) => false
+            a.equivalent(&Output::OUT { content_raw: String::new(), narsese: None }, &ignoring) => false
+        }
+    }
+}
@@ -0,0 +1,669 @@
+//! 定义「NAVM输出」的匹配器
+//! * 🎯以紧凑的模式字符串筛选输出，而非手写`match`并比对原始内容的子串
+//! * 📌模式语法：`<输出类型> [<内容模式>] [<数值条件>...]`
+//!   * 输出类型：如`ANSWER`；可用`|`并列多个，如`ANSWER|OUT`；`*`表示任意类型
+//!   * 内容模式
+//!     * Narsese：与[`Output::get_narsese`]的词项比对；带标点时亦比对标点
+//!       * `_`或`?`：通配符，匹配任意子项
+//!       * `?名称`：查询变量，捕获对应子项；同名变量须捕获相同的子项
+//!     * 操作：与[`Output::get_operation`]比对
+//!       * `^left`：操作名；`^_`匹配任意操作名
+//!       * `^left/2`：操作名+参数个数
+//!       * `^left({SELF}, ?x)`：操作名+参数模式（参数同样支持通配符与查询变量）
+//!   * 数值条件：`键 比较符 数值`，如`c >= 0.5` `f<0.2`
+//!     * 键：真值`f` `c`、预算值`p` `d` `q`
+//!     * 比较符：`>=` `<=` `>` `<` `=`
+//!     * 输出中没有相应数值⇒不满足
+//! * 📄`ANSWER <?x --> C> c>=0.5`
+//! * 📄`EXE ^left({SELF}, ?x)`
+
+use super::{Operation, Output};
+use crate::{
+    cmd::{ParseError, ParseErrorKind, ParseResult, Span},
+    util::split_top_level,
+};
+use narsese::{
+    api::GetTerm,
+    conversion::string::impl_lexical::format_instances::FORMAT_ASCII,
+    lexical::{Narsese as LexicalNarsese, Term as LexicalTerm},
+};
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+/// 匹配任意输出类型的标记
+pub const ANY_TYPE: &str = "*";
+
+/// 通配符（占位符）的前缀
+const WILDCARD_PREFIX: &str = "_";
+
+/// 查询变量的前缀
+/// * 📌名称为空时亦作通配符
+const QUERY_VAR_PREFIX: &str = "?";
+
+/// 变量绑定
+/// * 🚩查询变量名（不含`?`）→所捕获的子项
+pub type Bindings = HashMap<String, LexicalTerm>;
+
+/// 拆分出各个单词及其范围
+/// * 📌以空白符分隔
+pub(crate) fn split_words(s: &str) -> Vec<(&str, Span)> {
+    let mut result = vec![];
+    let mut start = None;
+    for (i, c) in s.char_indices().chain([(s.len(), ' ')]) {
+        match (c.is_whitespace(), start) {
+            (true, Some(begin)) => {
+                result.push((&s[begin..i], begin..i));
+                start = None;
+            }
+            (false, None) => start = Some(i),
+            _ => (),
+        }
+    }
+    result
+}
+
+/// 数值条件中的键
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValueKey {
+    /// 频率`f`
+    Frequency,
+    /// 信度`c`
+    Confidence,
+    /// 优先级`p`
+    Priority,
+    /// 耐久度`d`
+    Durability,
+    /// 质量`q`
+    Quality,
+}
+
+impl ValueKey {
+    /// 所有的键及其名称
+    const NAMES: [(ValueKey, char); 5] = [
+        (ValueKey::Frequency, 'f'),
+        (ValueKey::Confidence, 'c'),
+        (ValueKey::Priority, 'p'),
+        (ValueKey::Durability, 'd'),
+        (ValueKey::Quality, 'q'),
+    ];
+
+    /// 名称
+    pub fn name(&self) -> char {
+        Self::NAMES
+            .iter()
+            .find(|(key, _)| key == self)
+            .map(|(_, name)| *name)
+            .unwrap()
+    }
+
+    /// 从名称获取
+    pub fn from_name(name: char) -> Option<Self> {
+        Self::NAMES
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|(key, _)| *key)
+    }
+
+    /// 从输出中取值
+    /// * 🚩没有/缺省/数值非法⇒[`None`]
+    pub fn get(&self, output: &Output) -> Option<f64> {
+        let truth = || output.truth().ok().flatten();
+        let budget = || output.budget().ok().flatten();
+        match self {
+            ValueKey::Frequency => truth().map(|t| t.f),
+            ValueKey::Confidence => truth().and_then(|t| t.c),
+            ValueKey::Priority => budget().map(|b| b.p),
+            ValueKey::Durability => budget().and_then(|b| b.d),
+            ValueKey::Quality => budget().and_then(|b| b.q),
+        }
+    }
+}
+
+/// 比较符
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Comparison {
+    /// `>=`
+    Ge,
+    /// `<=`
+    Le,
+    /// `>`
+    Gt,
+    /// `<`
+    Lt,
+    /// `=`
+    Eq,
+}
+
+impl Comparison {
+    /// 所有的比较符及其写法
+    /// * ⚠️双字符者在前：先尝试`>=`再尝试`>`
+    const SYMBOLS: [(Comparison, &'static str); 5] = [
+        (Comparison::Ge, ">="),
+        (Comparison::Le, "<="),
+        (Comparison::Gt, ">"),
+        (Comparison::Lt, "<"),
+        (Comparison::Eq, "="),
+    ];
+
+    /// 写法
+    pub fn symbol(&self) -> &'static str {
+        Self::SYMBOLS
+            .iter()
+            .find(|(comparison, _)| comparison == self)
+            .map(|(_, symbol)| *symbol)
+            .unwrap()
+    }
+
+    /// 比较
+    pub fn compare(&self, left: f64, right: f64) -> bool {
+        match self {
+            Comparison::Ge => left >= right,
+            Comparison::Le => left <= right,
+            Comparison::Gt => left > right,
+            Comparison::Lt => left < right,
+            Comparison::Eq => left == right,
+        }
+    }
+}
+
+/// 数值条件
+/// * 📄`c >= 0.5`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValueCondition {
+    /// 键
+    pub key: ValueKey,
+    /// 比较符
+    pub comparison: Comparison,
+    /// 阈值
+    pub value: f64,
+}
+
+impl ValueCondition {
+    /// 尝试从（去掉空白后的）字符串解析
+    /// * 🚩不合语法⇒[`None`]
+    fn try_parse(s: &str) -> Option<Self> {
+        let mut chars = s.chars();
+        let key = ValueKey::from_name(chars.next()?)?;
+        let rest = chars.as_str();
+        let (comparison, value) = Comparison::SYMBOLS
+            .iter()
+            .find_map(|(comparison, symbol)| Some((*comparison, rest.strip_prefix(symbol)?)))?;
+        Some(Self {
+            key,
+            comparison,
+            value: value.parse().ok()?,
+        })
+    }
+
+    /// 输出是否满足此条件
+    pub fn is_satisfied(&self, output: &Output) -> bool {
+        self.key
+            .get(output)
+            .is_some_and(|value| self.comparison.compare(value, self.value))
+    }
+}
+
+impl Display for ValueCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (key, symbol) = (self.key.name(), self.comparison.symbol());
+        write!(f, "{key}{symbol}{}", self.value)
+    }
+}
+
+/// 操作模式
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperationPattern {
+    /// 操作名（不带尖号）
+    /// * 🚩[`None`]⇒任意操作名
+    pub name: Option<String>,
+    /// 参数个数
+    /// * 🚩[`None`]⇒任意个数
+    pub arity: Option<usize>,
+    /// 参数模式
+    /// * 🚩[`None`]⇒不比对参数
+    pub params: Option<Vec<LexicalTerm>>,
+}
+
+impl OperationPattern {
+    /// 匹配操作
+    pub fn match_operation(&self, operation: &Operation) -> Option<Bindings> {
+        if let Some(name) = &self.name {
+            if *name != operation.operator_name {
+                return None;
+            }
+        }
+        if let Some(arity) = self.arity {
+            if arity != operation.params.len() {
+                return None;
+            }
+        }
+        let mut bindings = Bindings::new();
+        if let Some(params) = &self.params {
+            if params.len() != operation.params.len() {
+                return None;
+            }
+            for (pattern, param) in params.iter().zip(&operation.params) {
+                if !match_term(pattern, param, &mut bindings) {
+                    return None;
+                }
+            }
+        }
+        Some(bindings)
+    }
+
+    /// 从（不带尖号的）字符串解析
+    /// * 📌出错范围相对于传入的字符串
+    fn parse(s: &str) -> ParseResult<Self> {
+        let invalid = |message: &str| {
+            ParseError::new(
+                ParseErrorKind::InvalidArgument(format!("{message}：^{s}")),
+                0..s.len(),
+            )
+        };
+        let parse_name = |name: &str| match name {
+            "" => Err(invalid("缺少操作名")),
+            WILDCARD_PREFIX => Ok(None),
+            name => Ok(Some(name.to_owned())),
+        };
+        // 参数模式
+        if let Some((name, args)) = s.split_once('(') {
+            let args = args
+                .strip_suffix(')')
+                .ok_or_else(|| invalid("参数列表未闭合"))?;
+            let params = split_top_level(args, ',')
+                .into_iter()
+                .filter(|arg| !arg.trim().is_empty())
+                .map(|arg| {
+                    FORMAT_ASCII
+                        .parse(arg.trim())
+                        .ok()
+                        .and_then(|narsese| narsese.try_into_term().ok())
+                        .ok_or_else(|| invalid("参数不是合法的词项"))
+                })
+                .collect::<ParseResult<Vec<_>>>()?;
+            return Ok(Self {
+                name: parse_name(name)?,
+                arity: Some(params.len()),
+                params: Some(params),
+            });
+        }
+        // 参数个数
+        let (name, arity) = match s.split_once('/') {
+            Some((name, arity)) => (
+                name,
+                Some(arity.parse().map_err(|_| invalid("参数个数不合法"))?),
+            ),
+            None => (s, None),
+        };
+        Ok(Self {
+            name: parse_name(name)?,
+            arity,
+            params: None,
+        })
+    }
+}
+
+impl Display for OperationPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "^{}", self.name.as_deref().unwrap_or(WILDCARD_PREFIX))?;
+        match (&self.params, self.arity) {
+            (Some(params), _) => {
+                let params = params
+                    .iter()
+                    .map(|param| FORMAT_ASCII.format(param))
+                    .collect::<Vec<_>>();
+                write!(f, "({})", params.join(", "))
+            }
+            (None, Some(arity)) => write!(f, "/{arity}"),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// 输出内容的模式
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContentPattern {
+    /// 任意内容
+    Any,
+    /// Narsese
+    Narsese {
+        /// 词项模式
+        term: LexicalTerm,
+        /// 标点
+        /// * 🚩[`None`]⇒不比对标点
+        punctuation: Option<String>,
+    },
+    /// 操作
+    Operation(OperationPattern),
+}
+
+/// 以模式匹配词项，并记录变量绑定
+/// * 🚩逐层结构化比对
+fn match_term(pattern: &LexicalTerm, term: &LexicalTerm, bindings: &mut Bindings) -> bool {
+    use LexicalTerm::*;
+    let match_terms = |patterns: &[LexicalTerm], terms: &[LexicalTerm], bindings: &mut Bindings| {
+        patterns.len() == terms.len()
+            && patterns
+                .iter()
+                .zip(terms)
+                .all(|(pattern, term)| match_term(pattern, term, bindings))
+    };
+    match (pattern, term) {
+        // 通配符
+        (Atom { prefix, name }, _)
+            if name.is_empty() && (prefix == WILDCARD_PREFIX || prefix == QUERY_VAR_PREFIX) =>
+        {
+            true
+        }
+        // 查询变量
+        (Atom { prefix, name }, term) if prefix == QUERY_VAR_PREFIX => match bindings.get(name) {
+            Some(bound) => bound == term,
+            None => {
+                bindings.insert(name.clone(), term.clone());
+                true
+            }
+        },
+        (
+            Compound { connecter, terms },
+            Compound {
+                connecter: connecter_,
+                terms: terms_,
+            },
+        ) => connecter == connecter_ && match_terms(terms, terms_, bindings),
+        (
+            Set {
+                left_bracket,
+                terms,
+                right_bracket,
+            },
+            Set {
+                left_bracket: left_bracket_,
+                terms: terms_,
+                right_bracket: right_bracket_,
+            },
+        ) => {
+            left_bracket == left_bracket_
+                && right_bracket == right_bracket_
+                && match_terms(terms, terms_, bindings)
+        }
+        (
+            Statement {
+                copula,
+                subject,
+                predicate,
+            },
+            Statement {
+                copula: copula_,
+                subject: subject_,
+                predicate: predicate_,
+            },
+        ) => {
+            copula == copula_
+                && match_term(subject, subject_, bindings)
+                && match_term(predicate, predicate_, bindings)
+        }
+        // 原子词项等：直接比对
+        (pattern, term) => pattern == term,
+    }
+}
+
+/// 获取词法Narsese中的标点
+/// * 🚩词项⇒[`None`]
+fn get_punctuation(narsese: &LexicalNarsese) -> Option<&str> {
+    match narsese {
+        LexicalNarsese::Term(..) => None,
+        LexicalNarsese::Sentence(sentence) => Some(&sentence.punctuation),
+        LexicalNarsese::Task(task) => Some(&task.sentence.punctuation),
+    }
+}
+
+/// NAVM输出匹配器
+/// * 🚩输出类型+内容模式+数值条件，三者均满足才算匹配
+/// * 📌构造：[`OutputMatcher::parse`]，或直接使用结构体字面量
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputMatcher {
+    /// 输出类型（全大写）
+    /// * 🚩[`None`]⇒任意类型
+    pub types: Option<Vec<String>>,
+    /// 内容模式
+    pub content: ContentPattern,
+    /// 数值条件
+    pub conditions: Vec<ValueCondition>,
+}
+
+impl OutputMatcher {
+    /// 从模式字符串解析
+    /// * 📌出错范围相对于模式字符串
+    pub fn parse(pattern: &str) -> ParseResult<Self> {
+        let words = split_words(pattern);
+        // 输出类型
+        let Some((types_word, types_span)) = words.first().cloned() else {
+            let kind = ParseErrorKind::MissingArgument {
+                index: 0,
+                expected: 1,
+            };
+            return Err(ParseError::new(kind, 0..pattern.len()));
+        };
+        let types = match types_word {
+            ANY_TYPE => None,
+            types_word => {
+                let types = types_word
+                    .split('|')
+                    .map(str::to_uppercase)
+                    .collect::<Vec<_>>();
+                if types.iter().any(String::is_empty) {
+                    let kind =
+                        ParseErrorKind::InvalidArgument(format!("输出类型不合法：{types_word}"));
+                    return Err(ParseError::new(kind, types_span));
+                }
+                Some(types)
+            }
+        };
+        // 数值条件：从末尾向前，每次尝试由1~3个单词拼成（允许比较符两侧有空白）
+        let mut end = words.len();
+        let mut conditions = vec![];
+        'conditions: while end > 1 {
+            for n in 1..=3.min(end - 1) {
+                let joined = words[end - n..end]
+                    .iter()
+                    .map(|(word, _)| *word)
+                    .collect::<String>();
+                if let Some(condition) = ValueCondition::try_parse(joined.trim_end_matches(',')) {
+                    conditions.push(condition);
+                    end -= n;
+                    continue 'conditions;
+                }
+            }
+            break;
+        }
+        conditions.reverse();
+        // 内容模式：输出类型之后、数值条件之前
+        let content_end = words.get(end).map_or(pattern.len(), |(_, span)| span.start);
+        let content_str = &pattern[types_span.end..content_end];
+        let content_start = types_span.end + (content_str.len() - content_str.trim_start().len());
+        let content_str = content_str.trim();
+        let content_span = content_start..content_start + content_str.len();
+        let content = match content_str {
+            "" => ContentPattern::Any,
+            s if s.starts_with('^') => ContentPattern::Operation(
+                OperationPattern::parse(&s[1..]).map_err(|e| e.offset(content_start + 1))?,
+            ),
+            s => {
+                let narsese = FORMAT_ASCII
+                    .parse(s)
+                    .map_err(|e| ParseError::new(ParseErrorKind::NarseseSyntax(e), content_span))?;
+                ContentPattern::Narsese {
+                    term: narsese.get_term().clone(),
+                    punctuation: get_punctuation(&narsese).map(str::to_owned),
+                }
+            }
+        };
+        Ok(Self {
+            types,
+            content,
+            conditions,
+        })
+    }
+
+    /// 输出类型是否符合
+    pub fn matches_type(&self, output: &Output) -> bool {
+        match &self.types {
+            Some(types) => types.iter().any(|t| output.is_type(t)),
+            None => true,
+        }
+    }
+
+    /// 以内容模式匹配Narsese
+    /// * 🚩内容模式为操作⇒不匹配
+    pub fn match_narsese(&self, narsese: &LexicalNarsese) -> Option<Bindings> {
+        match &self.content {
+            ContentPattern::Any => Some(Bindings::new()),
+            ContentPattern::Narsese { term, punctuation } => {
+                if let Some(punctuation) = punctuation {
+                    if get_punctuation(narsese) != Some(punctuation) {
+                        return None;
+                    }
+                }
+                let mut bindings = Bindings::new();
+                match_term(term, narsese.get_term(), &mut bindings).then_some(bindings)
+            }
+            ContentPattern::Operation(..) => None,
+        }
+    }
+
+    /// 以内容模式匹配操作
+    /// * 🚩内容模式为Narsese⇒不匹配
+    pub fn match_operation(&self, operation: &Operation) -> Option<Bindings> {
+        match &self.content {
+            ContentPattern::Any => Some(Bindings::new()),
+            ContentPattern::Operation(pattern) => pattern.match_operation(operation),
+            ContentPattern::Narsese { .. } => None,
+        }
+    }
+
+    /// 匹配输出
+    /// * 🚩匹配⇒变量绑定；不匹配⇒[`None`]
+    pub fn matches(&self, output: &Output) -> Option<Bindings> {
+        if !self.matches_type(output) {
+            return None;
+        }
+        let bindings = match &self.content {
+            ContentPattern::Any => Bindings::new(),
+            ContentPattern::Narsese { .. } => self.match_narsese(output.get_narsese()?)?,
+            ContentPattern::Operation(..) => self.match_operation(output.get_operation()?)?,
+        };
+        self.conditions
+            .iter()
+            .all(|condition| condition.is_satisfied(output))
+            .then_some(bindings)
+    }
+
+    /// 是否匹配输出
+    pub fn is_match(&self, output: &Output) -> bool {
+        self.matches(output).is_some()
+    }
+}
+
+impl FromStr for OutputMatcher {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl Display for OutputMatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.types {
+            Some(types) => write!(f, "{}", types.join("|"))?,
+            None => write!(f, "{ANY_TYPE}")?,
+        }
+        match &self.content {
+            ContentPattern::Any => (),
+            ContentPattern::Narsese { term, punctuation } => {
+                let punctuation = punctuation.as_deref().unwrap_or_default();
+                write!(f, " {}{punctuation}", FORMAT_ASCII.format(term))?
+            }
+            ContentPattern::Operation(pattern) => write!(f, " {pattern}")?,
+        }
+        for condition in &self.conditions {
+            write!(f, " {condition}")?;
+        }
+        Ok(())
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nar_dev_utils::asserts;
+    use narsese::lexical_atom;
+
+    /// 构造一个带Narsese的输出
+    fn output(type_name: &str, narsese: &str) -> Output {
+        let narsese = Some(FORMAT_ASCII.parse(narsese).unwrap());
+        let content_raw = String::new();
+        match type_name {
+            "ANSWER" => Output::ANSWER {
+                content_raw,
+                narsese,
+            },
+            _ => Output::OUT {
+                content_raw,
+                narsese,
+            },
+        }
+    }
+
+    #[test]
+    fn test_match_narsese() {
+        let answer = output("ANSWER", "<(&&, A, B) --> C>. %1.0;0.81%");
+        let matcher = |pattern: &str| OutputMatcher::parse(pattern).unwrap();
+        let bindings = matcher("ANSWER <(&&, ?x, ?y) --> C>")
+            .matches(&answer)
+            .unwrap();
+        asserts! {
+            bindings["x"] => lexical_atom!("A")
+            bindings["y"] => lexical_atom!("B")
+            // 类型
+            matcher("ANSWER|OUT").is_match(&answer) => true
+            matcher("*").is_match(&answer) => true
+            matcher("OUT").is_match(&answer) => false
+            // 通配符、标点
+            matcher("answer <_ --> C>.").is_match(&answer) => true
+            matcher("ANSWER <? --> C>?").is_match(&answer) => false
+            // 同名变量须一致
+            matcher("ANSWER <(&&, ?x, ?x) --> C>").is_match(&answer) => false
+            // 数值条件
+            matcher("ANSWER <_ --> C> c >= 0.5").is_match(&answer) => true
+            matcher("ANSWER <_ --> C> f=1, c>0.9").is_match(&answer) => false
+            matcher("ANSWER p>0").is_match(&answer) => false
+            // 格式化
+            matcher("answer <(&&, ?x, _) --> c>. c >= 0.5").to_string() => "ANSWER <(&&, ?x, _) --> c>. c>=0.5"
+        }
+    }
+
+    #[test]
+    fn test_match_operation() {
+        let exe = Output::EXE {
+            content_raw: String::new(),
+            operation: Operation::try_from_strings("left", ["{SELF}", "x"]).unwrap(),
+        };
+        let matcher = |pattern: &str| OutputMatcher::parse(pattern).unwrap();
+        let bindings = matcher("EXE ^left({SELF}, ?arg)").matches(&exe).unwrap();
+        asserts! {
+            bindings["arg"] => lexical_atom!("x")
+            matcher("EXE ^left").is_match(&exe) => true
+            matcher("EXE ^left/2").is_match(&exe) => true
+            matcher("EXE ^_/1").is_match(&exe) => false
+            matcher("EXE ^right").is_match(&exe) => false
+            matcher("EXE ^left(_)").is_match(&exe) => false
+            matcher("EXE <A --> B>").is_match(&exe) => false
+            matcher("EXE ^_({SELF}, _)").to_string() => "EXE ^_({SELF}, _)"
+        }
+        // 出错范围
+        let errors = ["", "EXE ^left(", "EXE ^left/x", "ANSWER <A -->"]
+            .map(|pattern| OutputMatcher::parse(pattern).unwrap_err().span());
+        assert_eq!(errors, [0..0, 5..10, 5..11, 7..13]);
+    }
+}
@@ -10,9 +10,32 @@
 mod structs;
 pub use structs::*;
 
+// 数值（真值、预算值、时态）
+mod values;
+pub use values::*;
+
+// 匹配器
+mod matcher;
+pub use matcher::*;
+
+// 纯文本格式
+mod text;
+pub use text::*;
+
+// 输出记录
+mod record;
+pub use record::*;
+
+// 语义等价
+mod equivalence;
+pub use equivalence::*;
+
 nar_dev_utils::mods! {
 
     // 转换
     // * 🚩【2024-04-09 10:28:32】现在要求使用`serde`
     "serde" => pub use conversion;
+
+    // JSON Schema
+    "serde_json" => pub use schema;
 }
@@ -0,0 +1,214 @@
+//! 定义「NAVM输出记录」
+//! * 🎯为输出附上「何时到达、来自何处」的信息
+//!   * 📄合并多个虚拟机/线程的会话后，仍能区分各输出的先后与来源
+//! * 📌[`OutputRecord`]只是信封：其中的[`Output`]保持不变
+//! * 🚩JSON形式扩展[`OutputJSON`]：在其字段之外追加`seq` `wallTime` `cycle` `source`
+
+use super::Output;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// NAVM输出记录
+/// * 🚩由[`crate::vm::StampedRuntime`]在拉取输出时生成
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputRecord {
+    /// 序号
+    /// * 📌同一来源内从`0`开始递增
+    pub seq: u64,
+
+    /// 到达时的系统时间
+    pub wall_time: SystemTime,
+
+    /// 到达时已输入的推理周期数
+    /// * 🚩[`None`]⇒未知
+    pub cycle: Option<usize>,
+
+    /// 来源
+    /// * 📄推理器名称，如`ONA` `OpenNARS#2`
+    pub source: String,
+
+    /// 输出本身
+    pub output: Output,
+}
+
+impl OutputRecord {
+    /// 构造函数
+    /// * 🚩以当前系统时间为到达时间
+    pub fn new(seq: u64, cycle: Option<usize>, source: impl Into<String>, output: Output) -> Self {
+        Self {
+            seq,
+            wall_time: SystemTime::now(),
+            cycle,
+            source: source.into(),
+            output,
+        }
+    }
+
+    /// 获取到达时间的UNIX毫秒时间戳
+    /// * 🚩早于UNIX纪元⇒`0`
+    pub fn wall_time_millis(&self) -> u64 {
+        self.wall_time
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_millis() as u64)
+    }
+
+    /// 从UNIX毫秒时间戳得到系统时间
+    pub fn wall_time_from_millis(millis: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(millis)
+    }
+}
+
+/// 输出记录中的输出
+/// * 🎯便于与[`Output`]一同作为迭代器元素使用
+impl From<OutputRecord> for Output {
+    fn from(record: OutputRecord) -> Self {
+        record.output
+    }
+}
+
+/// 与JSON的转换
+#[cfg(feature = "serde")]
+mod conversion {
+    use super::*;
+    use crate::output::OutputJSON;
+    use anyhow::Result;
+    use serde::{Deserialize, Serialize};
+
+    /// 用于统一存储「JSON化的NAVM输出记录」的结构
+    /// * 🚩通过`#[serde(flatten)]`将[`OutputJSON`]的字段展开至同层
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    #[derive(Debug, Default, Clone, PartialEq, Eq)]
+    pub struct OutputRecordJSON {
+        /// 序号
+        pub seq: u64,
+
+        /// 到达时间：UNIX毫秒时间戳
+        pub wall_time: u64,
+
+        /// 到达时已输入的推理周期数
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        pub cycle: Option<usize>,
+
+        /// 来源
+        pub source: String,
+
+        /// 输出本身
+        #[serde(flatten)]
+        pub output: OutputJSON,
+    }
+
+    impl OutputRecord {
+        /// 转换为JSON结构
+        /// * ⚠️到达时间仅保留到毫秒
+        pub fn to_json_struct(&self) -> OutputRecordJSON {
+            OutputRecordJSON {
+                seq: self.seq,
+                wall_time: self.wall_time_millis(),
+                cycle: self.cycle,
+                source: self.source.clone(),
+                output: self.output.to_json_struct(),
+            }
+        }
+
+        /// 尝试从JSON结构转换
+        pub fn try_from_json_struct(json: OutputRecordJSON) -> Result<Self> {
+            Ok(Self {
+                seq: json.seq,
+                wall_time: Self::wall_time_from_millis(json.wall_time),
+                cycle: json.cycle,
+                source: json.source,
+                output: Output::try_from_json_struct(json.output)?,
+            })
+        }
+
+        /// 转换为JSON字符串
+        #[cfg(feature = "serde_json")]
+        pub fn to_json_string(&self) -> String {
+            serde_json::to_string(self).expect("不会转换失败：内部JSON结构总是转换成功")
+        }
+
+        /// 尝试从JSON字符串解析
+        #[cfg(feature = "serde_json")]
+        pub fn try_from_json_string(s: &str) -> Result<Self> {
+            Ok(serde_json::from_str(s)?)
+        }
+    }
+
+    /// 对输出记录直接实现序列化
+    impl Serialize for OutputRecord {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            self.to_json_struct().serialize(serializer)
+        }
+    }
+
+    /// 对输出记录直接实现反序列化
+    impl<'de> Deserialize<'de> for OutputRecord {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            use serde::de::Error;
+            let json = OutputRecordJSON::deserialize(deserializer)?;
+            Self::try_from_json_struct(json).map_err(D::Error::custom)
+        }
+    }
+}
+#[cfg(feature = "serde")]
+pub use conversion::*;
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::tests::test_samples;
+
+    /// 产生测试用记录
+    /// * 🚩到达时间取整到毫秒，以便与JSON无损互转
+    #[cfg(feature = "serde_json")]
+    fn records() -> Vec<OutputRecord> {
+        test_samples()
+            .into_iter()
+            .enumerate()
+            .map(|(i, output)| OutputRecord {
+                wall_time: OutputRecord::wall_time_from_millis(1_700_000_000_000 + i as u64),
+                ..OutputRecord::new(i as u64, (i % 2 == 0).then_some(i), "mock", output)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_wall_time() {
+        let record = OutputRecord::new(0, None, "mock", test_samples().remove(0));
+        let millis = record.wall_time_millis();
+        assert!(millis > 0);
+        assert_eq!(
+            OutputRecord::wall_time_from_millis(millis)
+                .duration_since(UNIX_EPOCH)
+                .unwrap(),
+            Duration::from_millis(millis)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde_json")]
+    fn test_json() {
+        for record in records() {
+            let json = record.to_json_string();
+            println!("{json}");
+            // 扩展JSON化的NAVM输出：其字段展开在同一层
+            let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+            assert_eq!(value["type"], record.output.type_name());
+            assert_eq!(value["source"], "mock");
+            assert_eq!(value.get("cycle").is_some(), record.cycle.is_some());
+            // 可作为JSON化的NAVM输出解析
+            let output = Output::try_from_json_struct(serde_json::from_value(value).unwrap());
+            assert_eq!(output.unwrap(), record.output);
+            // 无损互转
+            assert_eq!(OutputRecord::try_from_json_string(&json).unwrap(), record);
+        }
+    }
+}
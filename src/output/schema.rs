@@ -0,0 +1,133 @@
+//! 提供「JSON化的NAVM输出」的JSON Schema
+//! * 🎯让其它语言的使用者以权威的Schema校验NAVM的JSON，而非各自手写、逐渐偏离
+//! * 📌Schema描述的是NAVM**产生**的JSON：字段名、可选字段与[`OutputJSON`]一致
+//! * 🚩遵循JSON Schema 2020-12
+//!
+//! [`OutputJSON`]: super::OutputJSON
+
+use super::{type_names, OutputJSON};
+use serde_json::{json, Value};
+
+/// 所用的JSON Schema方言
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// 构造「任意字符串，附带已知名称作示例」的Schema
+/// * 🎯列出已知名称，供使用者参考
+/// * ⚠️已知名称仅作注解（`examples`），不参与校验
+///   * 📄CIN特有的名称（如[`Output::UNCLASSIFIED`]的类别、自定义指令头）同样合法
+///
+/// [`Output::UNCLASSIFIED`]: super::Output::UNCLASSIFIED
+pub(crate) fn string_with_examples(known: &[&str], description: &str) -> Value {
+    json!({
+        "description": description,
+        "type": "string",
+        "examples": known,
+    })
+}
+
+/// 将「单个对象」的Schema包装为「对象数组」的Schema
+/// * 🚩原Schema置于`$defs`中，以`$ref`引用
+pub(crate) fn vec_schema(name: &str, item_schema: Value) -> Value {
+    json!({
+        "$schema": JSON_SCHEMA_DIALECT,
+        "title": format!("{name}[]"),
+        "type": "array",
+        "items": { "$ref": format!("#/$defs/{name}") },
+        "$defs": { name: item_schema },
+    })
+}
+
+impl OutputJSON {
+    /// 生成[`OutputJSON`]的JSON Schema
+    pub fn json_schema() -> Value {
+        json!({
+            "$schema": JSON_SCHEMA_DIALECT,
+            "title": "OutputJSON",
+            "description": "NAVM输出",
+            "type": "object",
+            "properties": {
+                "type": string_with_examples(&type_names::ALL, "输出的类别（全大写）"),
+                "content": {
+                    "description": "输出的（原始）内容",
+                    "type": "string",
+                },
+                "narsese": {
+                    "description": "输出所含的Narsese（ASCII CommonNarsese）",
+                    "type": "string",
+                },
+                "operation": {
+                    "description": "输出所含的NARS操作：[无尖号操作名, ...操作参数]",
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "minItems": 1,
+                },
+            },
+            "required": ["type", "content"],
+            "additionalProperties": false,
+            // `EXE`必定附带操作
+            "if": {
+                "properties": { "type": { "const": type_names::EXE } },
+                "required": ["type"],
+            },
+            "then": { "required": ["operation"] },
+        })
+    }
+
+    /// 生成「[`OutputJSON`]数组」的JSON Schema
+    /// * 🎯对应[`Output::vec_to_json_string`](super::Output::vec_to_json_string)
+    pub fn vec_json_schema() -> Value {
+        let mut item_schema = Self::json_schema();
+        item_schema.as_object_mut().unwrap().remove("$schema");
+        vec_schema("OutputJSON", item_schema)
+    }
+}
+
+/// 构造JSON Schema校验器
+/// * 🎯单元测试：以完整实现JSON Schema的校验器检验所生成的Schema
+/// * 🚩Schema本身不符合元Schema⇒panic
+#[cfg(test)]
+pub(crate) fn schema_validator(schema: &Value) -> jsonschema::Validator {
+    if let Err(e) = jsonschema::meta::validate(schema) {
+        panic!("Schema不符合元Schema：{e}");
+    }
+    jsonschema::validator_for(schema).unwrap()
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::{tests::test_samples, Output};
+
+    #[test]
+    fn test_json_schema() {
+        let schema = OutputJSON::json_schema();
+        println!("{}", serde_json::to_string_pretty(&schema).unwrap());
+        let validator = schema_validator(&schema);
+        let samples = test_samples();
+        // 每个样本均通过校验
+        for output in &samples {
+            let value = serde_json::to_value(output).unwrap();
+            if let Err(e) = validator.validate(&value) {
+                panic!("样本未通过校验：{e}");
+            }
+        }
+        // 数组形式
+        let value = serde_json::from_str(&Output::vec_to_json_string(&samples)).unwrap();
+        let vec_validator = schema_validator(&OutputJSON::vec_json_schema());
+        assert!(vec_validator.is_valid(&value));
+        // CIN特有的类别亦合法
+        assert!(validator.is_valid(&json!({ "type": "DECISION", "content": "" })));
+        // 不合法的JSON
+        for invalid in [
+            json!({ "type": "OUT" }),
+            json!({ "type": "EXE", "content": "" }),
+            json!({ "type": "EXE", "content": "", "operation": [] }),
+            json!({ "type": "OUT", "content": "", "extra": 1 }),
+            json!({ "type": 1, "content": "" }),
+        ] {
+            assert!(!validator.is_valid(&invalid), "{invalid}");
+        }
+        assert!(!vec_validator.is_valid(&json!([{}])));
+    }
+}
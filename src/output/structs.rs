@@ -4,6 +4,9 @@
 //! ! 📌【2024-03-27 19:29:44】现在移除输出类型`ANTICIPATE`，降格为`UNCLASSIFIED`
 //! * 原因：仅在特定CIN中出现，并不普遍适用于各CIN（并且在OpenNARS其中也只是插件）
 //!
+//! ! 📌【2026-10-17 04:05:11】重新引入`ANTICIPATE`，并一同引入`CONFIRM`、`DISAPPOINT`
+//! * 原因：NAL-7/8的实验需要直接匹配「预期」及其「确认/落空」，而非从`UNCLASSIFIED`中按字符串筛选
+//!
 //! # Reference
 //!
 //! **BabelNAR**中的如下Julia代码（旧）：
@@ -128,6 +131,42 @@ pub enum Output {
     /// * 📄最初见于PyNARS
     COMMENT { content: String },
 
+    /// 表示「产生一个预期」
+    /// * 🎯NAL-7/8中，系统对「将要发生的事件」的时间性预期
+    /// * 📄样例 @ OpenNARS: `ANTICIPATE: <{SELF} --> [satisfied]>`
+    ANTICIPATE {
+        /// 原始内容
+        content_raw: String,
+
+        /// （可能有的）Narsese内容（词法Narsese）
+        /// * ⚠️因CIN而异
+        narsese: Option<LexicalNarsese>,
+    },
+
+    /// 表示「预期得到确认」
+    /// * 🎯所预期的事件如期发生
+    /// * 📄样例 @ OpenNARS: `CONFIRM: <{SELF} --> [satisfied]>`
+    CONFIRM {
+        /// 原始内容
+        content_raw: String,
+
+        /// （可能有的）Narsese内容（词法Narsese）
+        /// * ⚠️因CIN而异
+        narsese: Option<LexicalNarsese>,
+    },
+
+    /// 表示「预期落空」
+    /// * 🎯所预期的事件未如期发生
+    /// * 📄样例 @ OpenNARS: `DISAPPOINT: <{SELF} --> [satisfied]>`
+    DISAPPOINT {
+        /// 原始内容
+        content_raw: String,
+
+        /// （可能有的）Narsese内容（词法Narsese）
+        /// * ⚠️因CIN而异
+        narsese: Option<LexicalNarsese>,
+    },
+
     /// 表示「CIN终止运行」
     /// * 🎯用于表征并告知「CIN终止」的情况
     ///   * 📌往往是NAVM运行时发出的最后一条消息
@@ -142,6 +181,7 @@ pub enum Output {
     /// * 🎯用于表示「可以识别到类型，但不在此枚举中」的NAVM输出
     ///   * 📌针对一些特定CIN的「方言」使用
     ///   * 📌针对后续「使用模式匹配识别出的类型」使用
+    /// * 📄样例 @ ONA: `decision expectation=0.507988 implication: <(<a --> b> &/ <(* {SELF}) --> ^left>) =/> <{SELF} --> [good]>>. ...`（`DECISION`）
     UNCLASSIFIED {
         r#type: String,
         content: String,
@@ -184,7 +224,19 @@ pub mod type_names {
     /// 输出类型名称 @ ANTICIPATE
     /// * 🚩【2024-04-11 22:58:00】仅出现在OpenNARS、ONA中的「半正式类型」
     /// * 📝【2024-04-11 22:58:45】禁用の考量：所涉及NAL层级（NAL-9）过高、不稳定，且输出往往无用
+    /// * 🚩【2026-10-17 04:05:11】现在重新作为正式类型[`super::Output::ANTICIPATE`]
     pub const ANTICIPATE: &str = "ANTICIPATE";
+    /// 输出类型名称 @ CONFIRM
+    pub const CONFIRM: &str = "CONFIRM";
+    /// 输出类型名称 @ DISAPPOINT
+    pub const DISAPPOINT: &str = "DISAPPOINT";
+
+    /// 所有已知的输出类型名称
+    /// * 📌不含[`super::Output::UNCLASSIFIED`]：其类型名称由CIN决定
+    pub const ALL: [&str; 13] = [
+        IN, OUT, ERROR, ANSWER, ACHIEVED, EXE, INFO, COMMENT, ANTICIPATE, CONFIRM, DISAPPOINT,
+        TERMINATED, OTHER,
+    ];
 }
 
 impl Output {
@@ -205,6 +257,9 @@ impl Output {
             Output::EXE { .. } => EXE,
             Output::INFO { .. } => INFO,
             Output::COMMENT { .. } => COMMENT,
+            Output::ANTICIPATE { .. } => ANTICIPATE,
+            Output::CONFIRM { .. } => CONFIRM,
+            Output::DISAPPOINT { .. } => DISAPPOINT,
             Output::TERMINATED { .. } => TERMINATED,
             // ! 特别的「未分类」情形：使用其中预置的「类名」
             Output::UNCLASSIFIED { r#type, .. } => r#type.as_str(),
@@ -248,6 +303,18 @@ impl Output {
                 content_raw: content,
                 ..
             }
+            | Output::ANTICIPATE {
+                content_raw: content,
+                ..
+            }
+            | Output::CONFIRM {
+                content_raw: content,
+                ..
+            }
+            | Output::DISAPPOINT {
+                content_raw: content,
+                ..
+            }
             | Output::INFO { message: content }
             | Output::TERMINATED {
                 description: content,
@@ -279,6 +346,9 @@ impl Output {
             | Output::OUT { narsese, .. }
             | Output::ANSWER { narsese, .. }
             | Output::ACHIEVED { narsese, .. }
+            | Output::ANTICIPATE { narsese, .. }
+            | Output::CONFIRM { narsese, .. }
+            | Output::DISAPPOINT { narsese, .. }
             // * 📝从`&Option<T>`变成`Option<&T>`的方法，直接使用[`Option::as_ref`]
             | Output::UNCLASSIFIED { narsese, .. } => narsese.as_ref(),
             // ! 使用通配符可能意味着后续「在别的类型中添加了Narsese字段，但不会被处理」的情况
@@ -499,6 +569,18 @@ pub mod tests {
             COMMENT {
                 content: "comment".into(),
             },
+            ANTICIPATE {
+                content_raw: "anticipate".into(),
+                narsese: Some(lexical_nse!(<{SELF} --> [satisfied]>.)),
+            },
+            CONFIRM {
+                content_raw: "confirm".into(),
+                narsese: Some(lexical_nse!(<{SELF} --> [satisfied]>.)),
+            },
+            DISAPPOINT {
+                content_raw: "disappoint".into(),
+                narsese: None,
+            },
             TERMINATED {
                 description: "terminated".into(),
             },
@@ -0,0 +1,379 @@
+//! 定义「NAVM输出」的纯文本格式
+//! * 🎯NAVM原生的纯文本输出协议：便于直接查看、`tail`日志
+//! * 📌单行，形如CIN日志：`类型: 内容`
+//!   * 📄`ANSWER: <A --> B>. %1.00;0.90%`
+//!   * 📄`EXE: <(*, {SELF}) --> ^left>`
+//!   * 📄`INFO: Loading RuleMap...`
+//! * 📌可往返：[`Display`]的结果可由[`FromStr`]无损解析回原输出
+//! * 🔗具体格式参见[`Output`]的[`Display`]实现
+
+use super::{type_names, Operation, Output};
+use anyhow::{anyhow, Result};
+use narsese::{
+    conversion::string::impl_lexical::format_instances::FORMAT_ASCII,
+    lexical::{Narsese as LexicalNarsese, Term as LexicalTerm},
+};
+use std::{fmt::Display, str::FromStr};
+
+/// 类型与内容的分隔符
+pub const TEXT_TYPE_SEPARATOR: &str = ": ";
+
+/// 「Narsese/操作」与原始内容的分隔符
+pub const TEXT_CONTENT_SEPARATOR: &str = "// ";
+
+/// 转义原始内容，使之保持单行
+fn escape(content: &str) -> String {
+    let mut result = String::with_capacity(content.len());
+    for c in content.chars() {
+        match c {
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// 反转义原始内容
+/// * 🚩无法识别的转义序列原样保留
+fn unescape(content: &str) -> String {
+    let mut result = String::with_capacity(content.len());
+    let mut chars = content.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('\\') => result.push('\\'),
+            Some('n') => result.push('\n'),
+            Some('r') => result.push('\r'),
+            Some(c) => {
+                result.push('\\');
+                result.push(c);
+            }
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+/// 呈现「主体 // 原始内容」
+/// * 🚩主体为空⇒`// 原始内容`
+/// * 🚩原始内容与主体相同，或需省略⇒仅主体
+fn fmt_with_raw(
+    f: &mut std::fmt::Formatter<'_>,
+    main: Option<String>,
+    content_raw: &str,
+) -> std::fmt::Result {
+    let content = escape(content_raw);
+    match main {
+        Some(main) if main == content_raw || f.alternate() => write!(f, "{main}"),
+        Some(main) => write!(f, "{main} {TEXT_CONTENT_SEPARATOR}{content}"),
+        None => write!(f, "{TEXT_CONTENT_SEPARATOR}{content}"),
+    }
+}
+
+/// 拆分「主体 // 原始内容」
+/// * 📌与[`fmt_with_raw`]相对
+/// * 🚩无分隔符⇒主体即原始内容（不反转义）
+fn split_with_raw(body: &str) -> (Option<&str>, String) {
+    let separator = TEXT_CONTENT_SEPARATOR.trim_end();
+    if body == separator {
+        return (None, String::new());
+    }
+    if let Some(content) = body.strip_prefix(TEXT_CONTENT_SEPARATOR) {
+        return (None, unescape(content));
+    }
+    match body.split_once(&format!(" {TEXT_CONTENT_SEPARATOR}")) {
+        Some((main, content)) => (Some(main), unescape(content)),
+        None => (Some(body), body.to_owned()),
+    }
+}
+
+/// 呈现为单行纯文本
+///
+/// # 格式
+///
+/// * 🚩类型：[`Output::type_name`]，以`: `与内容分隔
+/// * 🚩含Narsese的类别：`Narsese // 原始内容`
+///   * 原始内容与Narsese的ASCII形式相同⇒省略` // 原始内容`
+///   * 无Narsese⇒`// 原始内容`
+/// * 🚩`EXE`：`操作 // 原始内容`，操作以[`Operation`]的[`Display`]呈现
+///   * 原始内容与操作的呈现相同⇒省略` // 原始内容`
+/// * 🚩其它类别：原始内容
+/// * 📌原始内容中的`\`、换行符转义为`\\`、`\n`、`\r`，以保持单行
+/// * ⚠️[`Output::UNCLASSIFIED`]的类型若与已知类型同名，解析后将成为已知类型
+/// * ⚠️类型中不应含有`:`
+///
+/// 另：`{:#}`会省略「Narsese/操作」之后的原始内容，仅用于查看（不可往返）
+impl Display for Output {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{TEXT_TYPE_SEPARATOR}", self.type_name())?;
+        match self {
+            Output::EXE {
+                content_raw,
+                operation,
+            } => fmt_with_raw(f, Some(operation.to_string()), content_raw),
+            Output::IN { .. }
+            | Output::OUT { .. }
+            | Output::ANSWER { .. }
+            | Output::ACHIEVED { .. }
+            | Output::ANTICIPATE { .. }
+            | Output::CONFIRM { .. }
+            | Output::DISAPPOINT { .. }
+            | Output::UNCLASSIFIED { .. } => fmt_with_raw(
+                f,
+                self.get_narsese()
+                    .map(|narsese| FORMAT_ASCII.format(narsese)),
+                self.raw_content(),
+            ),
+            Output::ERROR { .. }
+            | Output::INFO { .. }
+            | Output::COMMENT { .. }
+            | Output::TERMINATED { .. }
+            | Output::OTHER { .. } => write!(f, "{}", escape(self.raw_content())),
+        }
+    }
+}
+
+/// 从单行纯文本解析
+/// * 🔗格式与[`Display`]实现相同
+/// * 🚩未知类型⇒[`Output::UNCLASSIFIED`]
+impl FromStr for Output {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (r#type, body) = s
+            .split_once(TEXT_TYPE_SEPARATOR.trim_end())
+            .ok_or_else(|| anyhow!("NAVM输出缺少类型：{s:?}"))?;
+        let body = body.strip_prefix(' ').unwrap_or(body);
+        if r#type.is_empty() {
+            return Err(anyhow!("NAVM输出的类型为空：{s:?}"));
+        }
+
+        // 无Narsese的类别
+        use type_names::*;
+        let content = || unescape(body);
+        match r#type {
+            ERROR => {
+                return Ok(Output::ERROR {
+                    description: content(),
+                })
+            }
+            INFO => return Ok(Output::INFO { message: content() }),
+            COMMENT => return Ok(Output::COMMENT { content: content() }),
+            TERMINATED => {
+                return Ok(Output::TERMINATED {
+                    description: content(),
+                })
+            }
+            OTHER => return Ok(Output::OTHER { content: content() }),
+            _ => (),
+        }
+
+        // 操作
+        let (main, content_raw) = split_with_raw(body);
+        if r#type == EXE {
+            let operation = main.ok_or_else(|| anyhow!("EXE输出缺少操作：{s:?}"))?;
+            return Ok(Output::EXE {
+                content_raw,
+                operation: operation.parse()?,
+            });
+        }
+
+        // 含Narsese的类别
+        let narsese = match main {
+            Some(narsese) => Some(FORMAT_ASCII.parse(narsese)?),
+            None => None,
+        };
+        let out = match r#type {
+            IN => Output::IN {
+                content: content_raw,
+                narsese,
+            },
+            OUT => Output::OUT {
+                content_raw,
+                narsese,
+            },
+            ANSWER => Output::ANSWER {
+                content_raw,
+                narsese,
+            },
+            ACHIEVED => Output::ACHIEVED {
+                content_raw,
+                narsese,
+            },
+            ANTICIPATE => Output::ANTICIPATE {
+                content_raw,
+                narsese,
+            },
+            CONFIRM => Output::CONFIRM {
+                content_raw,
+                narsese,
+            },
+            DISAPPOINT => Output::DISAPPOINT {
+                content_raw,
+                narsese,
+            },
+            _ => Output::UNCLASSIFIED {
+                r#type: r#type.to_owned(),
+                content: content_raw,
+                narsese,
+            },
+        };
+        Ok(out)
+    }
+}
+
+impl Operation {
+    /// 转换为「操作陈述」词项
+    /// * 📌与[`Operation`]的[`Display`]一致：`<(*, 参数...) --> ^操作名>`
+    pub fn to_term(&self) -> LexicalTerm {
+        LexicalTerm::Statement {
+            copula: "-->".into(),
+            subject: Box::new(LexicalTerm::Compound {
+                connecter: "*".into(),
+                terms: self.params.clone(),
+            }),
+            predicate: Box::new(LexicalTerm::Atom {
+                prefix: "^".into(),
+                name: self.operator_name.clone(),
+            }),
+        }
+    }
+
+    /// 尝试从「操作陈述」词项转换
+    /// * 📌与[`Operation`]的[`Display`]相对：`<(*, 参数...) --> ^操作名>`
+    pub fn try_from_term(term: LexicalTerm) -> Result<Self> {
+        let invalid = |term: &LexicalTerm| anyhow!("不是操作陈述：{}", FORMAT_ASCII.format(term));
+        let LexicalTerm::Statement {
+            copula,
+            subject,
+            predicate,
+        } = term
+        else {
+            return Err(invalid(&term));
+        };
+        match (copula.as_str(), *subject, *predicate) {
+            (
+                "-->",
+                LexicalTerm::Compound { connecter, terms },
+                LexicalTerm::Atom { prefix, name },
+            ) if connecter == "*" && prefix == "^" => Ok(Self::new(name, terms)),
+            (copula, subject, predicate) => Err(invalid(&LexicalTerm::Statement {
+                copula: copula.to_owned(),
+                subject: Box::new(subject),
+                predicate: Box::new(predicate),
+            })),
+        }
+    }
+}
+
+/// 从「操作陈述」字符串解析
+/// * 📌与[`Operation`]的[`Display`]相对
+impl FromStr for Operation {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match FORMAT_ASCII.parse(s)? {
+            LexicalNarsese::Term(term) => Self::try_from_term(term),
+            _ => Err(anyhow!("不是操作陈述：{s:?}")),
+        }
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{operation, output::tests::test_samples};
+    use narsese::lexical_nse;
+
+    /// 呈现后解析，应与原输出相同
+    fn assert_round_trip(output: &Output) {
+        let text = output.to_string();
+        assert!(!text.contains('\n'), "{text:?}");
+        let parsed = text.parse::<Output>().unwrap();
+        assert_eq!(&parsed, output, "{text:?}");
+    }
+
+    #[test]
+    fn test_display() {
+        let answer = Output::ANSWER {
+            content_raw: "<A --> B>. %1.00;0.90%".into(),
+            narsese: Some(lexical_nse!(<A --> B>. %1.00;0.90%)),
+        };
+        let exe = Output::EXE {
+            content_raw: "EXE: ^left({SELF})".into(),
+            operation: operation!("left" => "{SELF}"),
+        };
+        let disappoint = Output::DISAPPOINT {
+            content_raw: "disappoint".into(),
+            narsese: None,
+        };
+        let info = Output::INFO {
+            message: "a\\b\nc".into(),
+        };
+        assert_eq!(answer.to_string(), "ANSWER: <A --> B>. %1.00;0.90%");
+        assert_eq!(
+            exe.to_string(),
+            "EXE: <(*, {SELF}) --> ^left> // EXE: ^left({SELF})"
+        );
+        assert_eq!(format!("{exe:#}"), "EXE: <(*, {SELF}) --> ^left>");
+        assert_eq!(disappoint.to_string(), "DISAPPOINT: // disappoint");
+        assert_eq!(info.to_string(), "INFO: a\\\\b\\nc");
+        for output in [answer, exe, disappoint, info] {
+            assert_round_trip(&output);
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for output in test_samples() {
+            assert_round_trip(&output);
+        }
+        for output in [
+            Output::OUT {
+                content_raw: String::new(),
+                narsese: None,
+            },
+            Output::OTHER {
+                content: String::new(),
+            },
+            Output::EXE {
+                content_raw: "<(*) --> ^left>".into(),
+                operation: operation!("left"),
+            },
+            Output::UNCLASSIFIED {
+                r#type: "DECISION".into(),
+                content: "a // b".into(),
+                narsese: None,
+            },
+        ] {
+            assert_round_trip(&output);
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        // 冒号后的空格可省略
+        assert_eq!(
+            "ERROR:err".parse::<Output>().unwrap(),
+            Output::ERROR {
+                description: "err".into()
+            }
+        );
+        // 不合法的文本
+        for invalid in [
+            "no type",
+            ": content",
+            "EXE: // raw",
+            "EXE: <A --> B>",
+            "ANSWER: <A --> B",
+        ] {
+            assert!(invalid.parse::<Output>().is_err(), "{invalid:?}");
+        }
+    }
+}
@@ -0,0 +1,247 @@
+//! 定义从「词法Narsese」中提取的数值结构
+//! * 🎯让调用者直接拿到数值化的真值、预算值与时态，而无需自行解析字符串
+//! * 📌词法Narsese中这些值均以字符串存储，故提取时可能失败
+//!   * 🚩失败时返回错误，而非静默忽略
+//! * 📌缺省形式（如单真值`%1.0%`、单预算值`$0.5$`）亦合法
+//!   * 🚩缺省的分量为[`None`]：其默认值因CIN而异，不在此擅自补全
+
+use super::Output;
+use anyhow::{anyhow, Result};
+use narsese::lexical::{Narsese as LexicalNarsese, Sentence as LexicalSentence};
+
+/// 解析单个数值
+/// * 🚩附带「是什么值」以便报错
+/// * 🚩须在`[0, 1]`范围内
+fn parse_value(value: &str, name: &str) -> Result<f64> {
+    let parsed = value
+        .trim()
+        .parse::<f64>()
+        .map_err(|e| anyhow!("{name}「{value}」不是合法的数值：{e}"))?;
+    match (0.0..=1.0).contains(&parsed) {
+        true => Ok(parsed),
+        false => Err(anyhow!("{name}「{value}」不在[0, 1]范围内")),
+    }
+}
+
+/// 解析可缺省的数值
+fn parse_optional_value(value: Option<&String>, name: &str) -> Result<Option<f64>> {
+    value.map(|value| parse_value(value, name)).transpose()
+}
+
+/// 数值化的真值
+/// * 📌频率`f`+信度`c`
+/// * 📌信度可缺省：单真值`%1.0%`
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Truth {
+    /// 频率
+    pub f: f64,
+    /// 信度
+    pub c: Option<f64>,
+}
+
+impl Truth {
+    /// 从词法真值（字符串数组）解析
+    /// * 🚩空真值⇒[`None`]
+    /// * 🚩单真值⇒信度为[`None`]
+    /// * 🚩多于2个值、数值非法⇒[`Err`]
+    pub fn try_from_lexical(truth: &[String]) -> Result<Option<Self>> {
+        match truth {
+            [] => Ok(None),
+            [f, rest @ ..] if rest.len() <= 1 => Ok(Some(Self {
+                f: parse_value(f, "频率")?,
+                c: parse_optional_value(rest.first(), "信度")?,
+            })),
+            _ => Err(anyhow!("真值至多有2个值，实际为{truth:?}")),
+        }
+    }
+}
+
+/// 数值化的预算值
+/// * 📌优先级`p`+耐久度`d`+质量`q`
+/// * 📌耐久度、质量可缺省：`$0.5$` `$0.5;0.5$`
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Budget {
+    /// 优先级
+    pub p: f64,
+    /// 耐久度
+    pub d: Option<f64>,
+    /// 质量
+    pub q: Option<f64>,
+}
+
+impl Budget {
+    /// 从词法预算值（字符串数组）解析
+    /// * 🚩空预算⇒[`None`]
+    /// * 🚩缺省的分量⇒[`None`]
+    /// * 🚩多于3个值、数值非法⇒[`Err`]
+    pub fn try_from_lexical(budget: &[String]) -> Result<Option<Self>> {
+        match budget {
+            [] => Ok(None),
+            [p, rest @ ..] if rest.len() <= 2 => Ok(Some(Self {
+                p: parse_value(p, "优先级")?,
+                d: parse_optional_value(rest.first(), "耐久度")?,
+                q: parse_optional_value(rest.get(1), "质量")?,
+            })),
+            _ => Err(anyhow!("预算值至多有3个值，实际为{budget:?}")),
+        }
+    }
+}
+
+/// 时态
+/// * 📌对应词法Narsese中的「时间戳」
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Tense {
+    /// 永恒：无时间戳
+    Eternal,
+    /// 现在：`:|:`
+    Present,
+    /// 过去：`:\:`
+    Past,
+    /// 将来：`:/:`
+    Future,
+    /// 固定时刻：`:!123:`
+    Fixed(isize),
+}
+
+impl Tense {
+    /// 从词法时间戳（ASCII CommonNarsese）解析
+    pub fn try_from_lexical(stamp: &str) -> Result<Self> {
+        Ok(match stamp.trim() {
+            "" => Tense::Eternal,
+            ":|:" => Tense::Present,
+            r":\:" => Tense::Past,
+            ":/:" => Tense::Future,
+            stamp => match stamp
+                .strip_prefix(":!")
+                .and_then(|rest| rest.strip_suffix(':'))
+            {
+                Some(time) => Tense::Fixed(
+                    time.parse()
+                        .map_err(|e| anyhow!("时间戳「{stamp}」中的时刻不合法：{e}"))?,
+                ),
+                None => return Err(anyhow!("无法识别的时间戳「{stamp}」")),
+            },
+        })
+    }
+}
+
+/// 获取词法Narsese中的语句
+/// * 🚩词项⇒[`None`]
+fn get_sentence(narsese: &LexicalNarsese) -> Option<&LexicalSentence> {
+    match narsese {
+        LexicalNarsese::Term(..) => None,
+        LexicalNarsese::Sentence(sentence) => Some(sentence),
+        LexicalNarsese::Task(task) => Some(&task.sentence),
+    }
+}
+
+impl Output {
+    /// 获取数值化的真值
+    /// * 🚩无Narsese/Narsese为词项/真值为空⇒`Ok(None)`
+    /// * 🚩真值中有非法数值⇒[`Err`]
+    pub fn truth(&self) -> Result<Option<Truth>> {
+        match self.get_narsese().and_then(get_sentence) {
+            Some(sentence) => Truth::try_from_lexical(&sentence.truth),
+            None => Ok(None),
+        }
+    }
+
+    /// 获取数值化的预算值
+    /// * 🚩仅「任务」有预算值；其它情况⇒`Ok(None)`
+    pub fn budget(&self) -> Result<Option<Budget>> {
+        match self.get_narsese() {
+            Some(LexicalNarsese::Task(task)) => Budget::try_from_lexical(&task.budget),
+            _ => Ok(None),
+        }
+    }
+
+    /// 获取时态
+    /// * 🚩无Narsese/Narsese为词项⇒`Ok(None)`
+    pub fn tense(&self) -> Result<Option<Tense>> {
+        match self.get_narsese().and_then(get_sentence) {
+            Some(sentence) => Tense::try_from_lexical(&sentence.stamp).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nar_dev_utils::asserts;
+    use narsese::lexical_nse;
+
+    /// 构造一个「回答」输出
+    fn answer(narsese: LexicalNarsese) -> Output {
+        Output::ANSWER {
+            content_raw: String::new(),
+            narsese: Some(narsese),
+        }
+    }
+
+    #[test]
+    fn test_values() {
+        let output = answer(lexical_nse!($0.5;0.75;0.4$ <A --> B>. :|: %1.00;0.90%));
+        asserts! {
+            output.truth().unwrap() => Some(Truth { f: 1.0, c: Some(0.9) })
+            output.budget().unwrap() => Some(Budget { p: 0.5, d: Some(0.75), q: Some(0.4) })
+            output.tense().unwrap() => Some(Tense::Present)
+        }
+        let output = answer(lexical_nse!(<A --> B>.));
+        asserts! {
+            output.truth().unwrap() => None
+            output.budget().unwrap() => None
+            output.tense().unwrap() => Some(Tense::Eternal)
+        }
+        // 缺省形式
+        let output = answer(lexical_nse!($0.5$ <A --> B>. %1.0%));
+        asserts! {
+            output.truth().unwrap() => Some(Truth { f: 1.0, c: None })
+            output.budget().unwrap() => Some(Budget { p: 0.5, d: None, q: None })
+        }
+        let output = answer(lexical_nse!($0.5;0.25$ <A --> B>.));
+        assert_eq!(
+            output.budget().unwrap(),
+            Some(Budget {
+                p: 0.5,
+                d: Some(0.25),
+                q: None
+            })
+        );
+        let output = answer(lexical_nse!(<A --> B>));
+        asserts! {
+            output.truth().unwrap() => None
+            output.tense().unwrap() => None
+        }
+        let output = Output::INFO {
+            message: "info".into(),
+        };
+        assert_eq!(output.truth().unwrap(), None);
+        // 固定时刻
+        assert_eq!(
+            Tense::try_from_lexical(":!-12:").unwrap(),
+            Tense::Fixed(-12)
+        );
+    }
+
+    #[test]
+    fn test_values_error() {
+        // 非法数值
+        let truth = ["1.0".to_string(), "abc".to_string()];
+        let e = Truth::try_from_lexical(&truth).unwrap_err();
+        println!("{e}");
+        // 超出范围
+        assert!(Truth::try_from_lexical(&["1.5".into()]).is_err());
+        assert!(Truth::try_from_lexical(&["1.0".into(), "-0.1".into()]).is_err());
+        assert!(Budget::try_from_lexical(&["0.5".into(), "NaN".into()]).is_err());
+        // 个数过多
+        let too_many = ["0.5", "0.5", "0.5"].map(String::from);
+        assert!(Truth::try_from_lexical(&too_many).is_err());
+        let too_many = ["0.5", "0.5", "0.5", "0.5"].map(String::from);
+        assert!(Budget::try_from_lexical(&too_many).is_err());
+        // 时间戳不合法
+        assert!(Tense::try_from_lexical(":!x:").is_err());
+        assert!(Tense::try_from_lexical(":?:").is_err());
+    }
+}
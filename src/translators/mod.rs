@@ -0,0 +1,22 @@
+//! 负责「NAVM指令/输出」与各CIN原生IO之间的转译
+//! * 📄最初这些转译器定义在**BabelNAR** [^1] 中
+//! * 🎯让各CIN的对接者无需重复编写「指令→输入行」「输出行→NAVM输出」的映射
+//! * 📌每个CIN一个子模块，其中的结构体同时实现各转译特征
+//!
+//! [^1]: <https://github.com/ARCJ137442/BabelNAR.rs>
+
+nar_dev_utils::mod_and_pub_use! {
+    // 结构
+    structs
+    // 特征
+    traits
+    // 具体CIN
+    opennars
+    ona
+    pynars
+    nars_python
+    openjunars
+}
+
+// 工具函数
+mod utils;
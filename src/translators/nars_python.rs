@@ -0,0 +1,78 @@
+//! NARS-Python的转译器
+//! * 🔗<https://github.com/ccrock4t/NARS-Python>
+
+use super::{
+    utils::translate_typed_line,
+    InputTranslator, OutputTranslator, TranslateError, TranslateResult,
+};
+use crate::{cmd::Cmd, output::Output};
+
+/// NARS-Python的转译器
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NARSPython;
+
+impl NARSPython {
+    /// CIN名称
+    pub const NAME: &'static str = "NARS-Python";
+}
+
+impl InputTranslator for NARSPython {
+    fn translate_input(&mut self, cmd: &Cmd) -> TranslateResult<Vec<String>> {
+        let line = match cmd {
+            // 直接使用「指令尾」：自动格式化任务（兼容「空预算」）
+            Cmd::NSE(..) => cmd.tail(),
+            // 推理步进：纯数字
+            Cmd::CYC(n) => n.to_string(),
+            // 注释⇒无输入
+            Cmd::REM { .. } => return Ok(vec![]),
+            // 其它
+            _ => return Err(TranslateError::unsupported(Self::NAME, cmd)),
+        };
+        Ok(vec![line])
+    }
+}
+
+/// 输出：通用的`类型: Narsese`形式
+/// * 🚩`EXE`行中，优先取首个词作为操作，其次整体解析
+impl OutputTranslator for NARSPython {
+    fn translate_output(&mut self, line: &str) -> Output {
+        translate_typed_line(line, &[])
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operation;
+    use nar_dev_utils::asserts;
+    use narsese::lexical_nse;
+
+    /// 测试/输入转译
+    #[test]
+    fn test_input() {
+        let mut t = NARSPython;
+        let mut translate = |line: &str| t.translate_input(&Cmd::parse(line).unwrap());
+        asserts! {
+            translate("NSE <A --> B>.") => Ok(vec!["<A --> B>.".into()])
+            translate("CYC 5") => Ok(vec!["5".into()])
+            translate("REM comment") => Ok(vec![])
+        }
+        assert!(translate("VOL 0").is_err());
+    }
+
+    /// 测试/输出转译
+    #[test]
+    fn test_output() {
+        let mut t = NARSPython;
+        let mut translate = |line: &str| t.translate_output(line);
+        asserts! {
+            translate("OUT: <A --> C>. %1.00;0.81%").get_narsese()
+                => Some(&lexical_nse!(<A --> C>. %1.00;0.81%))
+            translate("ANSWER: <A --> C>. %1.00;0.81%").is_type("ANSWER") => true
+            translate("EXE: ^left based on desirability: 0.9").get_operation()
+                => Some(&operation!("left"))
+            translate("something else").is_type("OTHER") => true
+        }
+    }
+}
@@ -0,0 +1,525 @@
+//! ONA（OpenNARS for Applications）的转译器
+//! * 🔗<https://github.com/opennars/OpenNARS-for-Applications>
+
+use super::{
+    utils::{output_of_type, parse_narsese, parse_term, split_head},
+    InputTranslator, OutputTranslator, TranslateError, TranslateResult,
+};
+use crate::{
+    cmd::{
+        get_cmd_params_spanned, Cmd, CmdRegistry, ExtCmd, ParseError, ParseErrorKind,
+        ParseResult, TypedCmd,
+    },
+    output::{Operation, Output},
+    util::COPULAS,
+    vm::VmCapabilities,
+};
+use nar_dev_utils::JoinTo;
+use narsese::{
+    conversion::string::impl_lexical::format_instances::FORMAT_ASCII,
+    lexical::Narsese as LexicalNarsese,
+};
+
+/// ONA的转译器
+/// * 📌需记录「已注册的操作符」，以便为`*setopname`分配序号
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ONA {
+    /// 已注册的操作符（不带尖号）
+    /// * 🚩序号 = 索引 + 1
+    operators: Vec<String>,
+}
+
+impl ONA {
+    /// CIN名称
+    pub const NAME: &'static str = "ONA";
+
+    /// 可注册的操作符数量上限
+    /// * 📄ONA默认的`OPERATIONS_MAX`
+    pub const MAX_OPERATORS: usize = 10;
+
+    /// 「决策」输出的类型名
+    /// * 📄`decision expectation=0.5 implication: <a =/> b>. ...`
+    /// * 🚩作为[`Output::UNCLASSIFIED`]的类型
+    pub const DECISION: &'static str = "DECISION";
+
+    /// 构造函数
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 获取操作符的序号（从1开始）
+    /// * 🚩未注册⇒新分配；超出上限⇒报错
+    fn operator_index(&mut self, name: &str) -> TranslateResult<usize> {
+        if let Some(i) = self.operators.iter().position(|op| op == name) {
+            return Ok(i + 1);
+        }
+        if self.operators.len() >= Self::MAX_OPERATORS {
+            return Err(TranslateError::Invalid {
+                cin: Self::NAME,
+                reason: format!("操作符数量超出上限{}个：{name}", Self::MAX_OPERATORS),
+            });
+        }
+        self.operators.push(name.to_owned());
+        Ok(self.operators.len())
+    }
+
+    /// ONA的能力描述
+    /// * 📌复合词项最多有两个子项
+    /// * 📌指令头：本转译器支持的指令（含ONA特有指令）
+    pub fn capabilities() -> VmCapabilities {
+        let heads = ["NSE", "CYC", "VOL", "RES", "REG", "REM"]
+            .into_iter()
+            .chain([ONAMotorBabbling::HEAD, ONASetOpName::HEAD])
+            .map(str::to_uppercase);
+        VmCapabilities {
+            cmd_heads: Some(heads.collect()),
+            max_compound_arity: Some(2),
+            save_load: false,
+            ..Default::default()
+        }
+    }
+
+    /// ONA特有指令的注册表
+    /// * 🎯配合[`Cmd::parse_with`]，将ONA特有指令解析为强类型的扩展指令
+    pub fn cmd_registry() -> CmdRegistry {
+        let mut registry = CmdRegistry::new();
+        registry
+            .register::<ONAMotorBabbling>()
+            .register::<ONASetOpName>();
+        registry
+    }
+
+    /// 转译ONA特有的扩展指令
+    /// * 🚩非ONA特有⇒[`None`]
+    fn translate_ext(ext: &ExtCmd) -> Option<String> {
+        if let Some(babbling) = ext.downcast_ref::<ONAMotorBabbling>() {
+            return Some(format!("{}={}", ONAMotorBabbling::HEAD, babbling.format_tail()));
+        }
+        if let Some(set_op_name) = ext.downcast_ref::<ONASetOpName>() {
+            return Some(format!("{} {}", ONASetOpName::HEAD, set_op_name.format_tail()));
+        }
+        None
+    }
+}
+
+/// ONA特有指令：运动咿呀（motor babbling）
+/// * 📄`*motorbabbling 0.2` ⇒ `*motorbabbling=0.2`
+/// * 📄`*motorbabbling false` ⇒ `*motorbabbling=false`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ONAMotorBabbling {
+    /// 开关
+    Switch(bool),
+    /// 咿呀概率
+    Chance(f64),
+}
+
+impl TypedCmd for ONAMotorBabbling {
+    const HEAD: &'static str = "*motorbabbling";
+
+    fn parse_tail(tail: &str) -> ParseResult<Self> {
+        let [(param, span)] = get_cmd_params_spanned::<1>(tail)?;
+        Ok(match param.as_str() {
+            "true" => Self::Switch(true),
+            "false" => Self::Switch(false),
+            chance => Self::Chance(chance.parse().map_err(|_| {
+                ParseError::new(
+                    ParseErrorKind::InvalidArgument(format!("「{chance}」既非布尔值也非概率")),
+                    span,
+                )
+            })?),
+        })
+    }
+
+    fn format_tail(&self) -> String {
+        match self {
+            Self::Switch(switch) => switch.to_string(),
+            Self::Chance(chance) => chance.to_string(),
+        }
+    }
+}
+
+/// ONA特有指令：指定操作符的序号
+/// * 📄`*setopname 1 ^left` ⇒ `*setopname 1 ^left`
+/// * ⚠️原样转译，不影响[`Cmd::REG`]的序号分配
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ONASetOpName {
+    /// 序号（从1开始）
+    pub index: usize,
+    /// 操作符名（不带尖号）
+    pub operator: String,
+}
+
+impl TypedCmd for ONASetOpName {
+    const HEAD: &'static str = "*setopname";
+
+    fn parse_tail(tail: &str) -> ParseResult<Self> {
+        let [(index, index_span), (operator, _)] = get_cmd_params_spanned::<2>(tail)?;
+        let index = index
+            .parse()
+            .map_err(|e| ParseError::new(ParseErrorKind::InvalidNumber(e), index_span))?;
+        let operator = operator
+            .strip_prefix('^')
+            .map(str::to_owned)
+            .unwrap_or(operator);
+        Ok(Self { index, operator })
+    }
+
+    fn format_tail(&self) -> String {
+        format!("{} ^{}", self.index, self.operator)
+    }
+}
+
+impl InputTranslator for ONA {
+    fn translate_input(&mut self, cmd: &Cmd) -> TranslateResult<Vec<String>> {
+        let line = match cmd {
+            // ! ONA不支持预算值：只格式化其中的语句
+            Cmd::NSE(task) => FORMAT_ASCII.format(task.get_sentence()),
+            // 推理步进：纯数字
+            Cmd::CYC(n) => n.to_string(),
+            // 输出音量
+            Cmd::VOL(n) => format!("*volume={n}"),
+            // 重置
+            Cmd::RES { .. } => "*reset".into(),
+            // 注册操作符：分配序号
+            Cmd::REG { name } => format!("*setopname {} ^{name}", self.operator_index(name)?),
+            // 注释⇒无输入
+            Cmd::REM { .. } => return Ok(vec![]),
+            // ONA特有指令
+            Cmd::Ext(ext) => match Self::translate_ext(ext) {
+                Some(line) => line,
+                None => return Err(TranslateError::unsupported(Self::NAME, cmd)),
+            },
+            // 其它
+            _ => return Err(TranslateError::unsupported(Self::NAME, cmd)),
+        };
+        Ok(vec![line])
+    }
+}
+
+/// ONA输出中，Narsese之后附带的各类信息的标记
+const ONA_INFO_MARKERS: [&str; 6] = [
+    " Priority=",
+    " creationTime=",
+    " occurrenceTime=",
+    " Truth:",
+    " dt=",
+    " Stamp=",
+];
+
+/// ONA所用的（中缀）复合词项连接符
+/// * 📌【2026-10-17 04:04:34】`!`为ONA的否定，转换为CommonNarsese的`--`
+const ONA_CONNECTERS: [(&str, &str); 12] = [
+    ("&/", "&/"),
+    ("&|", "&|"),
+    ("&&", "&&"),
+    ("||", "||"),
+    ("*", "*"),
+    ("-", "-"),
+    ("~", "~"),
+    ("&", "&"),
+    ("|", "|"),
+    ("/", "/"),
+    ("\\", "\\"),
+    ("!", "--"),
+];
+
+/// 查找ONA连接符，并转换为CommonNarsese连接符
+fn ona_connecter(s: &str) -> Option<&'static str> {
+    ONA_CONNECTERS
+        .iter()
+        .find(|(ona, _)| *ona == s)
+        .map(|(_, common)| *common)
+}
+
+/// 按「顶层空格」拆分
+/// * 🚩统计各类括弧（含陈述括弧）的嵌套，并跳过系词
+fn split_top_level_spaces(s: &str) -> Vec<&str> {
+    let mut result = vec![];
+    let mut depth = 0usize;
+    let mut start = 0;
+    let mut i = 0;
+    while let Some(c) = s[i..].chars().next() {
+        // 跳过（前有空格的）系词
+        if let Some(copula) = COPULAS
+            .iter()
+            .find(|copula| s[i..].starts_with(**copula) && s[..i].ends_with(' '))
+        {
+            i += copula.len();
+            continue;
+        }
+        match c {
+            '(' | '<' | '[' | '{' => depth += 1,
+            ')' | '>' | ']' | '}' => depth = depth.saturating_sub(1),
+            ' ' if depth == 0 => {
+                result.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+        i += c.len_utf8();
+    }
+    result.push(&s[start..]);
+    result.retain(|part| !part.is_empty());
+    result
+}
+
+/// 将ONA的「中缀复合词项」转换为CommonNarsese的「前缀复合词项」
+/// * 📄`(a &/ b)` => `(&/, a, b)`
+/// * 📄`(* {SELF})` => `(*, {SELF})`
+/// * 🚩其它内容原样保留
+fn infix_to_prefix(s: &str) -> String {
+    let mut result = String::new();
+    let mut rest = s;
+    while let Some(open) = rest.find('(') {
+        result.push_str(&rest[..open]);
+        // 查找配对的右括号
+        let mut depth = 0usize;
+        let close = rest[open..].char_indices().find_map(|(i, c)| {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ => {}
+            }
+            (depth == 0).then_some(open + i)
+        });
+        let Some(close) = close else {
+            break;
+        };
+        result.push_str(&convert_group(&rest[open + 1..close]));
+        rest = &rest[close + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// 转换一个括号内的内容（不含括号本身）
+fn convert_group(inner: &str) -> String {
+    let parts = split_top_level_spaces(inner);
+    // 中缀：`a op b op c`
+    let infix_connecter = match parts.len() {
+        n if n >= 3 && n % 2 == 1 => ona_connecter(parts[1])
+            .filter(|_| parts.iter().skip(1).step_by(2).all(|op| *op == parts[1])),
+        _ => None,
+    };
+    if let Some(connecter) = infix_connecter {
+        let terms = parts.iter().step_by(2).map(|term| infix_to_prefix(term));
+        return format!("({connecter}, {})", terms.join_to_new(", "));
+    }
+    // 无逗号的前缀：`op a b`
+    if let [op, terms @ ..] = &parts[..] {
+        if let (Some(connecter), false) = (ona_connecter(op), terms.is_empty()) {
+            let terms = terms.iter().map(|term| infix_to_prefix(term));
+            return format!("({connecter}, {})", terms.join_to_new(", "));
+        }
+    }
+    // 其它：原样递归
+    format!("({})", infix_to_prefix(inner))
+}
+
+/// 从ONA的`Truth: frequency=1.000000, confidence=0.900000`中提取数值
+fn ona_truth_value<'a>(content: &'a str, key: &str) -> Option<&'a str> {
+    let (_, value) = content.split_once(key)?;
+    let end = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(value.len());
+    Some(&value[..end])
+}
+
+impl ONA {
+    /// 解析ONA输出中的Narsese
+    /// * 📄`<A --> C>. Priority=0.407250 Truth: frequency=1.000000, confidence=0.810000`
+    /// * 🚩截去附带信息，转换中缀复合词项，并将真值转换为CommonNarsese形式
+    pub fn parse_narsese(content: &str) -> Option<LexicalNarsese> {
+        let end = ONA_INFO_MARKERS
+            .iter()
+            .filter_map(|marker| content.find(marker))
+            .min()
+            .unwrap_or(content.len());
+        let mut narsese = infix_to_prefix(&content[..end]);
+        if let (Some(f), Some(c)) = (
+            ona_truth_value(content, "frequency="),
+            ona_truth_value(content, "confidence="),
+        ) {
+            narsese.push_str(&format!(" %{f};{c}%"));
+        }
+        parse_narsese(&narsese)
+    }
+
+    /// 解析ONA的操作
+    /// * 📄`^left executed with args ({SELF} * ball)`
+    /// * 📄`^left executed with args`
+    pub fn parse_operation(line: &str) -> Option<Operation> {
+        let name = line.strip_prefix('^')?.split_whitespace().next()?;
+        let args = line.split_once(" executed with args").map_or("", |(_, args)| args);
+        let params = match args.trim() {
+            "" => vec![],
+            args => match parse_term(&infix_to_prefix(args))? {
+                // 乘积⇒展开为多个参数
+                narsese::lexical::Term::Compound { connecter, terms } if connecter == "*" => terms,
+                term => vec![term],
+            },
+        };
+        Some(Operation::new(name, params))
+    }
+}
+
+impl OutputTranslator for ONA {
+    fn translate_output(&mut self, line: &str) -> Output {
+        let line = line.trim();
+        // 操作
+        if line.starts_with('^') && line.contains(" executed") {
+            if let Some(operation) = Self::parse_operation(line) {
+                return Output::EXE {
+                    content_raw: line.into(),
+                    operation,
+                };
+            }
+        }
+        // 「决策」⇒未归类的`DECISION`
+        // * 📌并非NAL-7/8意义上的「预期」：只是选取操作时所依据的蕴含
+        if let Some(rest) = line.strip_prefix("decision expectation=") {
+            let narsese = rest
+                .split_once("implication: ")
+                .and_then(|(_, implication)| Self::parse_narsese(implication));
+            return Output::UNCLASSIFIED {
+                r#type: Self::DECISION.into(),
+                content: line.into(),
+                narsese,
+            };
+        }
+        // 解析失败等导致的终止
+        if line == "Test failed." || line.starts_with("Parsing error") {
+            return Output::TERMINATED {
+                description: line.into(),
+            };
+        }
+        // `类型: 内容`形式
+        let Some((head, content)) = split_head(line) else {
+            return Output::OTHER {
+                content: line.into(),
+            };
+        };
+        let r#type = match head {
+            "Input" => "IN",
+            "Derived" | "Revised" => "OUT",
+            "Answer" => "ANSWER",
+            "Comment" => "COMMENT",
+            other => other,
+        };
+        output_of_type(r#type, line, Self::parse_narsese(content))
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operation;
+    use nar_dev_utils::asserts;
+    use narsese::lexical_nse;
+
+    /// 测试/输入转译
+    #[test]
+    fn test_input() {
+        let mut t = ONA::new();
+        let mut translate = |line: &str| t.translate_input(&Cmd::parse(line).unwrap());
+        asserts! {
+            translate("NSE $0.5;0.5;0.5$ <A --> B>. :|: %1.0;0.9%") => Ok(vec!["<A --> B>. :|: %1.0;0.9%".into()])
+            translate("CYC 5") => Ok(vec!["5".into()])
+            translate("VOL 0") => Ok(vec!["*volume=0".into()])
+            translate("REG left") => Ok(vec!["*setopname 1 ^left".into()])
+            translate("REG right") => Ok(vec!["*setopname 2 ^right".into()])
+            translate("REG left") => Ok(vec!["*setopname 1 ^left".into()])
+            translate("REM comment") => Ok(vec![])
+        }
+        // 超出上限
+        for i in 3..=ONA::MAX_OPERATORS {
+            assert!(translate(&format!("REG op{i}")).is_ok());
+        }
+        assert!(matches!(
+            translate("REG overflow"),
+            Err(TranslateError::Invalid { .. })
+        ));
+    }
+
+    /// 测试/ONA特有指令
+    #[test]
+    fn test_ext_cmds() {
+        let registry = ONA::cmd_registry();
+        let mut t = ONA::new();
+        let mut translate = |line: &str| t.translate_input(&registry.parse(line).unwrap());
+        asserts! {
+            translate("*motorbabbling 0.2") => Ok(vec!["*motorbabbling=0.2".into()])
+            translate("*motorbabbling false") => Ok(vec!["*motorbabbling=false".into()])
+            translate("*setopname 2 ^left") => Ok(vec!["*setopname 2 ^left".into()])
+            translate("*setopname 2 left") => Ok(vec!["*setopname 2 ^left".into()])
+        }
+        // 格式化→解析 无损
+        for line in ["*motorbabbling 0.2", "*motorbabbling true", "*setopname 1 ^left"] {
+            let cmd = registry.parse(line).unwrap();
+            assert!(matches!(cmd, Cmd::Ext(..)));
+            asserts! {
+                cmd.to_string() => line
+                registry.parse(&cmd.to_string()).unwrap() => cmd
+            }
+        }
+        // 参数不合法
+        for line in ["*motorbabbling abc", "*setopname x ^left", "*setopname 1"] {
+            assert!(registry.parse(line).is_err(), "{line}");
+        }
+    }
+
+    /// 测试/能力描述
+    #[test]
+    fn test_capabilities() {
+        let capabilities = ONA::capabilities();
+        let registry = ONA::cmd_registry();
+        let validate = |line: &str| registry.parse(line).unwrap().validate(&capabilities);
+        for line in ["NSE <(&/, a, b) =/> c>.", "CYC 1", "*motorbabbling 0.1", "REG left"] {
+            assert!(validate(line).is_ok(), "{line}");
+        }
+        for line in ["NSE <(&&, a, b, c) --> d>.", "SAV memory a.json", "NEW reasoner"] {
+            assert!(validate(line).is_err(), "{line}");
+        }
+    }
+
+    /// 测试/中缀转前缀
+    #[test]
+    fn test_infix_to_prefix() {
+        asserts! {
+            infix_to_prefix("<(a &/ b) =/> c>") => "<(&/, a, b) =/> c>"
+            infix_to_prefix("<(<a --> b> &/ <({SELF} * x) --> ^left>) =/> <c --> d>>")
+                => "<(&/, <a --> b>, <(*, {SELF}, x) --> ^left>) =/> <c --> d>>"
+            infix_to_prefix("(* {SELF})") => "(*, {SELF})"
+            infix_to_prefix("(! a)") => "(--, a)"
+            infix_to_prefix("(*, a, b)") => "(*, a, b)"
+        }
+    }
+
+    /// 测试/输出转译
+    #[test]
+    fn test_output() {
+        let mut t = ONA::new();
+        let mut translate = |line: &str| t.translate_output(line);
+        asserts! {
+            translate("Input: <A --> B>. Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000")
+                => Output::IN {
+                    content: "Input: <A --> B>. Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000".into(),
+                    narsese: Some(lexical_nse!(<A --> B>. %1.000000;0.900000%)),
+                }
+            translate("Derived: <(a &/ b) =/> c>. Priority=0.407250 Truth: frequency=1.000000, confidence=0.810000").get_narsese()
+                => Some(&lexical_nse!(<(&/, a, b) =/> c>. %1.000000;0.810000%))
+            translate("Answer: <B --> C>. creationTime=2 Truth: frequency=1.000000, confidence=0.810000").get_narsese()
+                => Some(&lexical_nse!(<B --> C>. %1.000000;0.810000%))
+            translate("Answer: None.").is_type("ANSWER") => true
+            translate("^left executed with args ({SELF} * ball)").get_operation()
+                => Some(&operation!("left" => "{SELF}" "ball"))
+            translate("^left executed with args").get_operation() => Some(&operation!("left"))
+            translate("decision expectation=0.507988 implication: <(<a --> b> &/ <(* {SELF}) --> ^left>) =/> <{SELF} --> [good]>>. Truth: frequency=0.992043 confidence=0.237070 dt=12.000000 precondition: <a --> b>. :|: Truth: frequency=1.000000 confidence=0.900000 occurrenceTime=171").get_narsese()
+                => Some(&lexical_nse!(<(&/, <a --> b>, <(*, {SELF}) --> ^left>) =/> <{SELF} --> [good]>>. %0.992043;0.237070%))
+            translate("decision expectation=0.5 implication: <a =/> b>. Truth: frequency=1.0 confidence=0.5").is_type("DECISION") => true
+            translate("Test failed.").is_type("TERMINATED") => true
+            translate("performing 3 inference steps:").is_type("OTHER") => true
+        }
+    }
+}
@@ -0,0 +1,87 @@
+//! OpenJunars的转译器
+//! * 🔗<https://github.com/AIxer/OpenJunars>
+
+use super::{
+    utils::translate_typed_line, InputTranslator, OutputTranslator, TranslateError,
+    TranslateResult,
+};
+use crate::{
+    cmd::Cmd,
+    output::{type_names, Output},
+};
+
+/// OpenJunars的转译器
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct OpenJunars;
+
+impl OpenJunars {
+    /// CIN名称
+    pub const NAME: &'static str = "OpenJunars";
+}
+
+impl InputTranslator for OpenJunars {
+    fn translate_input(&mut self, cmd: &Cmd) -> TranslateResult<Vec<String>> {
+        let line = match cmd {
+            // 直接使用「指令尾」：自动格式化任务（兼容「空预算」）
+            Cmd::NSE(..) => cmd.tail(),
+            // 推理步进：`:c`命令
+            Cmd::CYC(n) => format!(":c {n}"),
+            // 注释⇒无输入
+            Cmd::REM { .. } => return Ok(vec![]),
+            // 其它
+            _ => return Err(TranslateError::unsupported(Self::NAME, cmd)),
+        };
+        Ok(vec![line])
+    }
+}
+
+/// 输出：`类型: Narsese`形式
+/// * 📄`Input: <A --> B>. %1.00;0.90%`
+/// * 📄`Derived: <A --> C>. %1.00;0.81%`
+/// * 📄`Answer: <A --> C>. %1.00;0.81%`
+/// * 🚩`Input`⇒[`Output::IN`]，`Derived`⇒[`Output::OUT`]
+/// * 📌仅支持NAL 1~6：无操作输出
+impl OutputTranslator for OpenJunars {
+    fn translate_output(&mut self, line: &str) -> Output {
+        translate_typed_line(line, &[("Input", type_names::IN), ("Derived", type_names::OUT)])
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nar_dev_utils::asserts;
+    use narsese::lexical_nse;
+
+    /// 测试/输入转译
+    #[test]
+    fn test_input() {
+        let mut t = OpenJunars;
+        let mut translate = |line: &str| t.translate_input(&Cmd::parse(line).unwrap());
+        asserts! {
+            translate("NSE <A --> B>.") => Ok(vec!["<A --> B>.".into()])
+            translate("CYC 5") => Ok(vec![":c 5".into()])
+            translate("REM comment") => Ok(vec![])
+        }
+        assert!(translate("VOL 0").is_err());
+    }
+
+    /// 测试/输出转译
+    #[test]
+    fn test_output() {
+        let mut t = OpenJunars;
+        let mut translate = |line: &str| t.translate_output(line);
+        let input = translate("Input: <A --> B>. %1.00;0.90%");
+        let derived = translate("Derived: <A --> C>. %1.00;0.81%");
+        asserts! {
+            input.is_type("IN") => true
+            input.get_narsese() => Some(&lexical_nse!(<A --> B>. %1.00;0.90%))
+            derived.is_type("OUT") => true
+            derived.get_narsese() => Some(&lexical_nse!(<A --> C>. %1.00;0.81%))
+            derived.get_content() => "Derived: <A --> C>. %1.00;0.81%"
+            translate("Answer: <A --> C>. %1.00;0.81%").is_type("ANSWER") => true
+            translate("something else").is_type("OTHER") => true
+        }
+    }
+}
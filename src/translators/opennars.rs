@@ -0,0 +1,151 @@
+//! OpenNARS（3.x）的转译器
+//! * 🔗<https://github.com/opennars/opennars>
+
+use super::{
+    utils::{output_of_type, parse_narsese, split_head},
+    InputTranslator, OutputTranslator, TranslateError, TranslateResult,
+};
+use crate::{
+    cmd::Cmd,
+    output::{Operation, Output},
+    util::split_top_level,
+};
+use narsese::lexical::Narsese as LexicalNarsese;
+
+/// OpenNARS的转译器
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct OpenNARS;
+
+impl OpenNARS {
+    /// CIN名称
+    pub const NAME: &'static str = "OpenNARS";
+}
+
+impl InputTranslator for OpenNARS {
+    fn translate_input(&mut self, cmd: &Cmd) -> TranslateResult<Vec<String>> {
+        let line = match cmd {
+            // 直接使用「指令尾」：自动格式化任务（兼容「空预算」）
+            Cmd::NSE(..) => cmd.tail(),
+            // 推理步进：纯数字
+            Cmd::CYC(n) => n.to_string(),
+            // 输出音量
+            Cmd::VOL(n) => format!("*volume={n}"),
+            // 重置
+            Cmd::RES { .. } => "*reset".into(),
+            // 注释：OpenNARS使用`'`开头的注释行
+            Cmd::REM { comment } => format!("'{comment}"),
+            // 其它
+            _ => return Err(TranslateError::unsupported(Self::NAME, cmd)),
+        };
+        Ok(vec![line])
+    }
+}
+
+impl OpenNARS {
+    /// 解析OpenNARS输出中的Narsese
+    /// * 🚩去掉末尾的时间戳信息`{3404 : (-6241115378434429522,0)}`
+    pub fn parse_narsese(content: &str) -> Option<LexicalNarsese> {
+        let end = content
+            .match_indices(" {")
+            .filter(|(i, _)| content[i + 2..].starts_with(|c: char| c.is_ascii_digit()))
+            .last()
+            .map_or(content.len(), |(i, _)| i);
+        parse_narsese(&content[..end])
+    }
+
+    /// 解析OpenNARS的操作
+    /// * 📄`$0.45;0.90;0.95$ ^left([{SELF}, (*,P1,P2)])=null`
+    pub fn parse_operation(content: &str) -> Option<Operation> {
+        // 操作符名：`^`之后、`(`之前
+        let (_, operation) = content.split_once('^')?;
+        let (name, args) = operation.split_once('(')?;
+        // 参数列表：`[`与`]`之间
+        let args = args.strip_prefix('[')?;
+        let args = &args[..args.rfind(']')?];
+        let params = match args.trim() {
+            "" => vec![],
+            args => split_top_level(args, ','),
+        };
+        Operation::try_from_strings(name.trim(), params.into_iter().map(str::trim)).ok()
+    }
+}
+
+impl OutputTranslator for OpenNARS {
+    fn translate_output(&mut self, line: &str) -> Output {
+        let line = line.trim();
+        // 非`类型: 内容`形式⇒其它
+        let Some((head, content)) = split_head(line) else {
+            return Output::OTHER {
+                content: line.into(),
+            };
+        };
+        match head.to_uppercase().as_str() {
+            // 操作：使用OpenNARS特有的格式
+            "EXE" => match Self::parse_operation(content) {
+                Some(operation) => Output::EXE {
+                    content_raw: line.into(),
+                    operation,
+                },
+                None => Output::OTHER {
+                    content: line.into(),
+                },
+            },
+            // 其它：通用处理
+            r#type => output_of_type(r#type, line, Self::parse_narsese(content)),
+        }
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operation;
+    use nar_dev_utils::asserts;
+    use narsese::lexical_nse;
+
+    /// 测试/输入转译
+    #[test]
+    fn test_input() {
+        let mut t = OpenNARS;
+        let mut translate = |line: &str| t.translate_input(&Cmd::parse(line).unwrap());
+        asserts! {
+            translate("NSE <A --> B>.") => Ok(vec!["<A --> B>.".into()])
+            translate("CYC 5") => Ok(vec!["5".into()])
+            translate("VOL 0") => Ok(vec!["*volume=0".into()])
+            translate("REM the detective claims") => Ok(vec!["'the detective claims".into()])
+            translate("SAV memory a.json") => Err(TranslateError::Unsupported {
+                cin: OpenNARS::NAME,
+                head: "SAV".into()
+            })
+        }
+    }
+
+    /// 测试/输出转译
+    #[test]
+    fn test_output() {
+        let mut t = OpenNARS;
+        let mut translate = |line: &str| t.translate_output(line);
+        asserts! {
+            translate("IN: <A --> B>. %1.00;0.90% {0 : (-7995324758518856376,0)}").get_narsese()
+                => Some(&lexical_nse!(<A --> B>. %1.00;0.90%))
+            translate("OUT: <A1 --> A2>. %1.00;0.90% {3404 : (-6241115378434429522,0)}").get_narsese()
+                => Some(&lexical_nse!(<A1 --> A2>. %1.00;0.90%))
+            translate("Answer: <{B} --> C>. %1.00;0.81% {11778 : (-5483911157924289284,1);(-5483911157924289284,0)}")
+                => Output::ANSWER {
+                    content_raw: "Answer: <{B} --> C>. %1.00;0.81% {11778 : (-5483911157924289284,1);(-5483911157924289284,0)}".into(),
+                    narsese: Some(lexical_nse!(<{B} --> C>. %1.00;0.81%)),
+                }
+            translate("EXE: $0.45;0.90;0.95$ ^left([{SELF}, (*,P1,P2)])=null").get_operation()
+                => Some(&operation!("left" => "{SELF}" "(*,P1,P2)"))
+            translate("ERR: java.lang.ClassCastException").is_type("ERROR") => true
+            translate("ANTICIPATE: <{SELF} --> [satisfied]>") => Output::ANTICIPATE {
+                content_raw: "ANTICIPATE: <{SELF} --> [satisfied]>".into(),
+                narsese: Some(lexical_nse!(<{SELF} --> [satisfied]>)),
+            }
+            translate("DISAPPOINT: <{SELF} --> [satisfied]>").is_type("DISAPPOINT") => true
+            translate("Executed based on: $0.2680;0.0862;0.5836$ <A --> B>. %0.75;0.33%").is_type("OTHER") => true
+            translate("[l]: attaching Shell to Nar...").is_type("OTHER") => true
+        }
+    }
+}
@@ -0,0 +1,126 @@
+//! PyNARS的转译器
+//! * 🔗<https://github.com/bowen-xu/PyNARS>
+//! * 📌对接其`ConsolePlus`的命令
+
+use super::{
+    utils::{output_of_type, parse_narsese, split_head, strip_ansi},
+    InputTranslator, OutputTranslator, TranslateError, TranslateResult,
+};
+use crate::{cmd::Cmd, output::Output, vm::VmCapabilities};
+use narsese::lexical::Narsese as LexicalNarsese;
+
+/// PyNARS的转译器
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PyNARS;
+
+impl PyNARS {
+    /// CIN名称
+    pub const NAME: &'static str = "PyNARS";
+
+    /// PyNARS的能力描述
+    /// * 📌截止至2024-04-13，尚未正式支持NAL 7~9
+    pub fn capabilities() -> VmCapabilities {
+        let heads = ["NSE", "CYC", "VOL", "REG", "NEW", "DEL", "REM"];
+        VmCapabilities {
+            cmd_heads: Some(heads.into_iter().map(String::from).collect()),
+            max_nal_level: 6,
+            save_load: false,
+            ..Default::default()
+        }
+    }
+}
+
+impl InputTranslator for PyNARS {
+    fn translate_input(&mut self, cmd: &Cmd) -> TranslateResult<Vec<String>> {
+        let line = match cmd {
+            // 直接使用「指令尾」：自动格式化任务（兼容「空预算」）
+            Cmd::NSE(..) => cmd.tail(),
+            // 推理步进：纯数字
+            Cmd::CYC(n) => n.to_string(),
+            // 输出音量
+            Cmd::VOL(n) => format!("/volume {n}"),
+            // 注册操作符
+            Cmd::REG { name } => format!("/register {name}"),
+            // 新建/删除推理器
+            Cmd::NEW { target } => format!("/new {target}"),
+            Cmd::DEL { target } => format!("/delete {target}"),
+            // 注释⇒无输入
+            Cmd::REM { .. } => return Ok(vec![]),
+            // 其它
+            _ => return Err(TranslateError::unsupported(Self::NAME, cmd)),
+        };
+        Ok(vec![line])
+    }
+}
+
+impl PyNARS {
+    /// 解析PyNARS输出中的Narsese
+    /// * 🚩兼容真值中的`:`分隔符：`%1.000:0.900%` => `%1.000;0.900%`
+    pub fn parse_narsese(content: &str) -> Option<LexicalNarsese> {
+        let narsese = match content.split_once('%') {
+            Some((before, truth)) => format!("{before}%{}", truth.replace(':', ";")),
+            None => content.to_owned(),
+        };
+        parse_narsese(&narsese)
+    }
+}
+
+impl OutputTranslator for PyNARS {
+    fn translate_output(&mut self, line: &str) -> Output {
+        // 去掉颜色
+        let line = strip_ansi(line);
+        let line = line.trim();
+        // 跳过开头的预算值数字
+        let typed = line
+            .split_whitespace()
+            .find(|word| word.parse::<f64>().is_err())
+            .map_or("", |word| &line[word.as_ptr() as usize - line.as_ptr() as usize..]);
+        match split_head(typed) {
+            Some((head, content)) => output_of_type(head, line, Self::parse_narsese(content)),
+            None => Output::OTHER {
+                content: line.into(),
+            },
+        }
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operation;
+    use nar_dev_utils::asserts;
+    use narsese::lexical_nse;
+
+    /// 测试/输入转译
+    #[test]
+    fn test_input() {
+        let mut t = PyNARS;
+        let mut translate = |line: &str| t.translate_input(&Cmd::parse(line).unwrap());
+        asserts! {
+            translate("NSE <A --> B>.") => Ok(vec!["<A --> B>.".into()])
+            translate("CYC 5") => Ok(vec!["5".into()])
+            translate("REG left") => Ok(vec!["/register left".into()])
+            translate("NEW r") => Ok(vec!["/new r".into()])
+            translate("REM comment") => Ok(vec![])
+        }
+        assert!(translate("SAV memory a.json").is_err());
+    }
+
+    /// 测试/输出转译
+    #[test]
+    fn test_output() {
+        let mut t = PyNARS;
+        let mut translate = |line: &str| t.translate_output(line);
+        asserts! {
+            translate("\x1b[48;2;124;10;10m 0.90  0.90  0.50 \x1b[0m\x1b[38;2;10;124;10mOUT   :\x1b[0m<A-->C>. %1.000;0.810%").get_narsese()
+                => Some(&lexical_nse!(<A --> C>. %1.000;0.810%))
+            translate("ACHIEVED: A. :|: %1.000:0.900%").get_narsese()
+                => Some(&lexical_nse!(A. :|: %1.000;0.900%))
+            translate(" 0.90  0.90  0.50 EXE   :<(*, {SELF})-->^left>!").get_operation()
+                => Some(&operation!("left" => "{SELF}"))
+            translate("INFO  : Loading RuleMap <LUT.pkl>...").is_type("INFO") => true
+            translate("Setup: Changing random seed=137..").is_type("OTHER") => true
+        }
+    }
+}
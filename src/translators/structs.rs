@@ -0,0 +1,56 @@
+//! 定义与「转译器」有关的结构
+
+use crate::cmd::Cmd;
+use std::{error::Error, fmt::Display};
+
+/// 转译错误
+/// * 🎯让调用者区分「该CIN不支持此指令」与「指令内容不合法」
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TranslateError {
+    /// 该CIN不支持此类指令
+    /// * 📄ONA不支持`SAV`
+    Unsupported {
+        /// CIN名称
+        cin: &'static str,
+        /// 所涉及的指令头
+        head: String,
+    },
+
+    /// 该CIN支持此类指令，但指令内容无法转译
+    /// * 📄ONA可注册的操作符数量有限
+    Invalid {
+        /// CIN名称
+        cin: &'static str,
+        /// 原因
+        reason: String,
+    },
+}
+
+impl TranslateError {
+    /// 构造「不支持」错误
+    /// * 🚩从指令中取指令头
+    pub fn unsupported(cin: &'static str, cmd: &Cmd) -> Self {
+        TranslateError::Unsupported {
+            cin,
+            head: cmd.head().to_owned(),
+        }
+    }
+}
+
+impl Display for TranslateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TranslateError::Unsupported { cin, head } => {
+                write!(f, "NAVM指令「{head}」不被CIN「{cin}」支持")
+            }
+            TranslateError::Invalid { cin, reason } => {
+                write!(f, "NAVM指令无法转译到CIN「{cin}」：{reason}")
+            }
+        }
+    }
+}
+
+impl Error for TranslateError {}
+
+/// 简记的类型别名：转译结果
+pub type TranslateResult<T> = Result<T, TranslateError>;
@@ -0,0 +1,27 @@
+//! 定义「转译器」的特征
+
+use super::TranslateResult;
+use crate::{cmd::Cmd, output::Output};
+
+/// 输入转译器
+/// * 🎯将NAVM指令[`Cmd`]转换为CIN的原生输入
+/// * 📌一条指令可能对应零至多行输入
+///   * 📄[`Cmd::REM`]通常对应零行
+/// * 📌使用可变引用：部分CIN需要记录状态
+///   * 📄ONA的`*setopname`需要为操作符分配序号
+pub trait InputTranslator {
+    /// 【抽象】将指令转译为CIN原生输入行
+    /// * ⚠️返回的各行不带换行符
+    /// * 🚩不支持的指令⇒[`super::TranslateError::Unsupported`]
+    fn translate_input(&mut self, cmd: &Cmd) -> TranslateResult<Vec<String>>;
+}
+
+/// 输出转译器
+/// * 🎯将CIN的原生输出行转换为NAVM输出[`Output`]
+/// * 📌总是成功：无法识别的行归入[`Output::OTHER`]
+/// * 📌其中的Narsese统一转换为CommonNarsese的词法形式
+pub trait OutputTranslator {
+    /// 【抽象】将CIN的一行输出转译为NAVM输出
+    /// * ⚠️传入的行不应带换行符
+    fn translate_output(&mut self, line: &str) -> Output;
+}
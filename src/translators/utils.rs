@@ -0,0 +1,209 @@
+//! 转译器共用的工具函数
+//! * 🎯从CIN输出行中提取「输出类型」「Narsese」「操作」
+
+use crate::output::{type_names, Operation, Output};
+use narsese::{
+    conversion::string::impl_lexical::format_instances::FORMAT_ASCII,
+    lexical::{Narsese as LexicalNarsese, Term as LexicalTerm},
+};
+
+/// 拆分形如`HEAD: content`的行
+/// * 🚩头部须为非空的「字母/下划线」序列（允许其后带空白）
+/// * 📌返回的头部已去除空白，内容已去除首尾空白
+pub fn split_head(line: &str) -> Option<(&str, &str)> {
+    let (head, content) = line.split_once(':')?;
+    let head = head.trim();
+    match !head.is_empty() && head.chars().all(|c| c.is_ascii_alphabetic() || c == '_') {
+        true => Some((head, content.trim())),
+        false => None,
+    }
+}
+
+/// 去除ANSI转义序列
+/// * 🎯PyNARS等带颜色的输出
+pub fn strip_ansi(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            // `ESC [ ... 字母`
+            '\x1b' => {
+                if chars.next_if_eq(&'[').is_some() {
+                    for c in chars.by_ref() {
+                        if c.is_ascii_alphabetic() {
+                            break;
+                        }
+                    }
+                }
+            }
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// 尝试以ASCII CommonNarsese解析
+/// * 🚩解析失败⇒[`None`]
+pub fn parse_narsese(s: &str) -> Option<LexicalNarsese> {
+    match s.trim() {
+        "" => None,
+        s => FORMAT_ASCII.parse(s).ok(),
+    }
+}
+
+/// 尝试以ASCII CommonNarsese解析词项
+pub fn parse_term(s: &str) -> Option<LexicalTerm> {
+    parse_narsese(s)?.try_into_term().ok()
+}
+
+/// 从词项中提取操作
+/// * 📄`<(*, {SELF}, x) --> ^left>`
+/// * 📄`(^left, {SELF}, x)`
+/// * 📄`^left`（无参）
+pub fn operation_from_term(term: &LexicalTerm) -> Option<Operation> {
+    /// 从「操作符原子」中提取操作符名
+    fn operator_name(term: &LexicalTerm) -> Option<&str> {
+        match term {
+            LexicalTerm::Atom { prefix, name } if prefix == "^" => Some(name),
+            _ => None,
+        }
+    }
+    match term {
+        // 无参操作
+        LexicalTerm::Atom { .. } => Some(Operation::new(operator_name(term)?, [])),
+        // 陈述形式：乘积为主词，操作符为谓词
+        LexicalTerm::Statement {
+            copula,
+            subject,
+            predicate,
+        } if copula == "-->" => {
+            let name = operator_name(predicate)?;
+            match &**subject {
+                LexicalTerm::Compound { connecter, terms } if connecter == "*" => {
+                    Some(Operation::new(name, terms.iter().cloned()))
+                }
+                _ => None,
+            }
+        }
+        // 复合词项形式：首个组分为操作符
+        LexicalTerm::Compound { terms, .. } => {
+            let (first, params) = terms.split_first()?;
+            Some(Operation::new(
+                operator_name(first)?,
+                params.iter().cloned(),
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// 从（可能是语句/任务的）Narsese中提取操作
+pub fn operation_from_narsese(narsese: &LexicalNarsese) -> Option<Operation> {
+    let term = match narsese {
+        LexicalNarsese::Term(term) => term,
+        LexicalNarsese::Sentence(sentence) => &sentence.term,
+        LexicalNarsese::Task(task) => &task.sentence.term,
+    };
+    operation_from_term(term)
+}
+
+/// 按「输出类型名」构造输出
+/// * 🎯各CIN「`类型: 内容`」形式输出的通用处理
+/// * 📌原始内容均为整行
+/// * 🚩操作从Narsese中提取；提取不到的`EXE`归入[`Output::OTHER`]
+pub fn output_of_type(r#type: &str, line: &str, narsese: Option<LexicalNarsese>) -> Output {
+    use type_names::*;
+    let content = line.to_owned();
+    match r#type.to_uppercase().as_str() {
+        IN => Output::IN { content, narsese },
+        OUT => Output::OUT {
+            content_raw: content,
+            narsese,
+        },
+        ANSWER => Output::ANSWER {
+            content_raw: content,
+            narsese,
+        },
+        ACHIEVED => Output::ACHIEVED {
+            content_raw: content,
+            narsese,
+        },
+        EXE => match narsese.as_ref().and_then(operation_from_narsese) {
+            Some(operation) => Output::EXE {
+                content_raw: content,
+                operation,
+            },
+            None => Output::OTHER { content },
+        },
+        ERROR | "ERR" => Output::ERROR {
+            description: content,
+        },
+        INFO => Output::INFO { message: content },
+        COMMENT => Output::COMMENT { content },
+        ANTICIPATE => Output::ANTICIPATE {
+            content_raw: content,
+            narsese,
+        },
+        CONFIRM => Output::CONFIRM {
+            content_raw: content,
+            narsese,
+        },
+        DISAPPOINT => Output::DISAPPOINT {
+            content_raw: content,
+            narsese,
+        },
+        _ => Output::OTHER { content },
+    }
+}
+
+/// 转译形如`类型: Narsese`的输出行
+/// * 🎯NARS-Python、OpenJunars等输出格式简单的CIN
+/// * 🚩`aliases`：CIN特有的类型名→输出类型名（不区分大小写）
+///   * 📄OpenJunars的`Derived`⇒[`type_names::OUT`]
+/// * 🚩`EXE`行中，优先取首个词作为操作，其次整体解析
+/// * 🚩不符合该形式⇒[`Output::OTHER`]
+pub fn translate_typed_line(line: &str, aliases: &[(&str, &str)]) -> Output {
+    let line = line.trim();
+    let Some((head, content)) = split_head(line) else {
+        return Output::OTHER {
+            content: line.into(),
+        };
+    };
+    let head = aliases
+        .iter()
+        .find(|(alias, _)| head.eq_ignore_ascii_case(alias))
+        .map_or(head, |(_, r#type)| r#type);
+    let narsese = match head.eq_ignore_ascii_case(type_names::EXE) {
+        true => content
+            .split_whitespace()
+            .next()
+            .and_then(parse_narsese)
+            .or_else(|| parse_narsese(content)),
+        false => parse_narsese(content),
+    };
+    output_of_type(head, line, narsese)
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operation;
+    use nar_dev_utils::asserts;
+
+    #[test]
+    fn test_utils() {
+        asserts! {
+            split_head("OUT: <A --> B>.") => Some(("OUT", "<A --> B>."))
+            split_head("INFO  : Loading") => Some(("INFO", "Loading"))
+            split_head("<A --> B>. :|:") => None
+            strip_ansi("\x1b[48;2;10;10;10m 0.90 \x1b[0mOUT") => " 0.90 OUT"
+            operation_from_term(&parse_term("<(*, {SELF}, x) --> ^left>").unwrap())
+                => Some(operation!("left" => "{SELF}" "x"))
+            operation_from_term(&parse_term("^left").unwrap()) => Some(operation!("left"))
+            operation_from_term(&parse_term("<A --> B>").unwrap()) => None
+            translate_typed_line("Derived: <A --> C>.", &[("Derived", "OUT")]).is_type("OUT") => true
+            translate_typed_line("Derived: <A --> C>.", &[]).is_type("OTHER") => true
+        }
+    }
+}
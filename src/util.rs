@@ -0,0 +1,55 @@
+//! 库内共用的工具函数
+//! * 🎯供转译器、输出匹配器等模块共用，避免模块间相互依赖
+//! * 📌主要处理CommonNarsese字符串的「顶层」结构
+
+/// CommonNarsese的陈述系词
+/// * 🎯拆分时整体跳过，避免其中的`<` `>` `{` `]`等被当作括弧
+pub const COPULAS: [&str; 13] = [
+    "-->", "<->", "==>", "<=>", "{--", "--]", "{-]", "=/>", "=|>", "=\\>", "</>", "<|>", "<\\>",
+];
+
+/// 按「顶层分隔符」拆分
+/// * 🎯拆分`[{SELF}, (*,P1,P2)]`、`<A --> B>, C`这类参数列表
+/// * 🚩统计圆括号、方括号、花括号与陈述括弧的嵌套，并跳过系词
+pub fn split_top_level(s: &str, separator: char) -> Vec<&str> {
+    let mut result = vec![];
+    let mut depth = 0usize;
+    let mut start = 0;
+    let mut i = 0;
+    while let Some(c) = s[i..].chars().next() {
+        // 跳过系词
+        if let Some(copula) = COPULAS.iter().find(|copula| s[i..].starts_with(**copula)) {
+            i += copula.len();
+            continue;
+        }
+        match c {
+            '(' | '[' | '{' | '<' => depth += 1,
+            ')' | ']' | '}' | '>' => depth = depth.saturating_sub(1),
+            c if c == separator && depth == 0 => {
+                result.push(&s[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+        i += c.len_utf8();
+    }
+    result.push(&s[start..]);
+    result
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nar_dev_utils::asserts;
+
+    #[test]
+    fn test_split_top_level() {
+        asserts! {
+            split_top_level("{SELF}, (*,P1,P2)", ',') => vec!["{SELF}", " (*,P1,P2)"]
+            split_top_level("<A --> B>,<<a ==> b> <=> c>", ',') => vec!["<A --> B>", "<<a ==> b> <=> c>"]
+            split_top_level("<A {-- B>,<C --] D>,E", ',') => vec!["<A {-- B>", "<C --] D>", "E"]
+            split_top_level("", ',') => vec![""]
+        }
+    }
+}
@@ -0,0 +1,580 @@
+//! 引入「非公理虚拟机」的异步特征
+//! * 🎯供异步服务直接使用，而非以`spawn_blocking`包装同步的[`VmRuntime`]
+//! * 📌仅使用标准库中的[`Future`]：不依赖任何特定的执行器
+//!   * 🚩因此自行定义与`futures_core::Stream`同形的[`Stream`]
+//! * 🚩提供双向适配器
+//!   * 同步→异步：[`AsyncVmAdapter`]
+//!   * 异步→同步：[`BlockingVmAdapter`]
+
+use super::{VmCapabilities, VmLauncher, VmRuntime, VmStatus, FETCH_POLL_INTERVAL};
+use crate::{cmd::Cmd, output::Output};
+use anyhow::{anyhow, Result};
+use std::{
+    future::{poll_fn, Future},
+    pin::{pin, Pin},
+    sync::{
+        mpsc::{self, Sender},
+        Arc,
+    },
+    task::{Context, Poll, Wake, Waker},
+    thread::Thread,
+    time::{Duration, Instant},
+};
+
+/// 异步流
+/// * 📌与`futures_core::Stream`同形：可简单地互相适配
+/// * 🚩[`Poll::Ready`]中的[`None`]⇒流已结束
+pub trait Stream {
+    /// 流中的元素
+    type Item;
+
+    /// 尝试拉取下一个元素
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>>;
+}
+
+/// 异步虚拟机运行时
+/// * 🎯与[`VmRuntime`]相对的异步版本
+/// * 📌输入、终止为异步方法；输出为异步流[`Outputs`]
+/// * 📌实现时可直接使用`async fn`
+pub trait AsyncVmRuntime {
+    // 输入 //
+
+    /// 【抽象】向虚拟机输入NAVM指令
+    /// * 🔗[`VmRuntime::input_cmd`]
+    fn input_cmd(&mut self, cmd: Cmd) -> impl Future<Output = Result<()>> + Send;
+
+    // 输出 //
+
+    /// 【抽象】尝试拉取一个输出
+    /// * 🚩暂无输出⇒[`Poll::Pending`]，并在有输出时唤醒
+    /// * 🚩输出已结束（如虚拟机已终止）⇒[`Poll::Ready`]中的[`None`]
+    fn poll_output(&mut self, cx: &mut Context<'_>) -> Poll<Option<Output>>;
+
+    /// 获取输出流
+    /// * 📌输出顺序**从旧到新**
+    fn outputs(&mut self) -> Outputs<'_, Self>
+    where
+        Self: Sized,
+    {
+        Outputs { runtime: self }
+    }
+
+    /// 等待下一个输出
+    /// * 🚩输出已结束⇒[`None`]
+    fn next_output(&mut self) -> impl Future<Output = Option<Output>> + Send
+    where
+        Self: Send,
+    {
+        poll_fn(move |cx| self.poll_output(cx))
+    }
+
+    // 生命周期 //
+
+    /// 【抽象】获取虚拟机状态
+    /// * 🔗[`VmRuntime::status`]
+    fn status(&self) -> &VmStatus;
+
+    /// 判断虚拟机是否已终止
+    #[inline]
+    fn is_terminated(&self) -> bool {
+        matches!(self.status(), VmStatus::Terminated(..))
+    }
+
+    /// 【抽象】终止虚拟机
+    /// * 🔗[`VmRuntime::terminate`]
+    fn terminate(&mut self) -> impl Future<Output = Result<()>> + Send;
+
+    // 能力 //
+
+    /// 获取虚拟机的能力描述
+    /// * 🚩默认「无限制」
+    fn capabilities(&self) -> VmCapabilities {
+        VmCapabilities::default()
+    }
+}
+
+/// 异步虚拟机启动器
+/// * 🎯与[`VmLauncher`]相对的异步版本
+pub trait AsyncVmLauncher {
+    /// 将启动到的运行时
+    type Runtime: AsyncVmRuntime;
+
+    /// 从builder构建并启动运行时
+    /// * 🔗[`VmLauncher::launch`]
+    fn launch(self) -> impl Future<Output = Result<Self::Runtime>> + Send;
+
+    /// 获取将启动的虚拟机的能力描述
+    /// * 🚩默认「无限制」
+    fn capabilities(&self) -> VmCapabilities {
+        VmCapabilities::default()
+    }
+}
+
+/// 异步运行时的输出流
+/// * 🚩由[`AsyncVmRuntime::outputs`]创建
+#[derive(Debug)]
+pub struct Outputs<'a, R: AsyncVmRuntime> {
+    /// 所属的运行时
+    runtime: &'a mut R,
+}
+
+impl<R: AsyncVmRuntime> Stream for Outputs<'_, R> {
+    type Item = Output;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Output>> {
+        self.get_mut().runtime.poll_output(cx)
+    }
+}
+
+/// 轮询定时器
+/// * 🎯供[`AsyncVmAdapter`]在暂无输出时定时重新轮询，而不立即唤醒导致忙等
+/// * 🚩首次使用时启动后台线程，按请求顺序在[`FETCH_POLL_INTERVAL`]后唤醒
+///   * 📌延时相同⇒按请求顺序到期，依次等待、唤醒即可
+/// * 📌后台线程随定时器退出：定时器被丢弃⇒通道关闭⇒线程结束
+#[derive(Debug, Default)]
+struct PollTimer {
+    /// 向后台线程发送「到期时刻+唤醒器」
+    /// * 🚩尚未启动⇒[`None`]
+    sender: Option<Sender<(Instant, Waker)>>,
+}
+
+impl PollTimer {
+    /// 在[`FETCH_POLL_INTERVAL`]后唤醒
+    fn wake_after_poll_interval(&mut self, waker: Waker) {
+        let sender = self.sender.get_or_insert_with(|| {
+            let (sender, receiver) = mpsc::channel::<(Instant, Waker)>();
+            std::thread::spawn(move || {
+                for (deadline, waker) in receiver {
+                    if let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+                        std::thread::sleep(remaining);
+                    }
+                    waker.wake();
+                }
+            });
+            sender
+        });
+        // 后台线程意外退出⇒立即唤醒，并在下次请求时重启
+        if let Err(mpsc::SendError((_, waker))) =
+            sender.send((Instant::now() + FETCH_POLL_INTERVAL, waker))
+        {
+            self.sender = None;
+            waker.wake();
+        }
+    }
+
+    /// 停止后台线程
+    /// * 🚩关闭通道：线程唤醒剩余的等待者后退出
+    fn stop(&mut self) {
+        self.sender = None;
+    }
+}
+
+/// 同步→异步 适配器
+/// * 🚩包装[`VmRuntime`]⇒[`AsyncVmRuntime`]；包装[`VmLauncher`]⇒[`AsyncVmLauncher`]
+/// * ⚠️同步运行时无法在「有新输出」时通知，只能轮询
+///   * 🚩暂无输出⇒由适配器自有的后台线程在[`FETCH_POLL_INTERVAL`]（1毫秒）后唤醒，再次轮询
+///   * 📌后台线程在首次等待时启动，在输出流结束或适配器被丢弃时退出
+///   * ⚠️代价：等待输出期间，每毫秒调用一次[`VmRuntime::try_fetch_output`]，
+///     并经由后台线程唤醒一次任务；新输出最多延迟约一个轮询间隔
+///   * 💡输出稀疏、等待较久时，宜改用能主动唤醒的[`AsyncVmRuntime`]实现
+/// * ⚠️输入、终止仍在当前线程同步执行
+#[derive(Debug)]
+pub struct AsyncVmAdapter<T> {
+    /// 被包装的同步运行时/启动器
+    inner: T,
+    /// 轮询定时器
+    timer: PollTimer,
+    /// 输出流是否已结束
+    /// * 🎯拉取出错后不再重复拉取
+    finished: bool,
+}
+
+impl<T> AsyncVmAdapter<T> {
+    /// 包装同步运行时/启动器
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            timer: PollTimer::default(),
+            finished: false,
+        }
+    }
+
+    /// 获取被包装的同步运行时/启动器
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// 获取被包装的同步运行时/启动器（可变）
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// 解包，取回被包装的同步运行时/启动器
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<R: VmRuntime + Send> AsyncVmRuntime for AsyncVmAdapter<R> {
+    async fn input_cmd(&mut self, cmd: Cmd) -> Result<()> {
+        self.inner.input_cmd(cmd)
+    }
+
+    /// * 🚩拉取出错⇒作为[`Output::ERROR`]输出一次，此后结束输出流
+    /// * 🚩已终止⇒结束输出流
+    /// * 🚩暂无输出⇒[`Poll::Pending`]，并在一个轮询间隔后唤醒
+    fn poll_output(&mut self, cx: &mut Context<'_>) -> Poll<Option<Output>> {
+        if self.finished {
+            return Poll::Ready(None);
+        }
+        let (poll, finished) = match self.inner.try_fetch_output() {
+            Ok(Some(output)) => (Poll::Ready(Some(output)), false),
+            _ if self.inner.is_terminated() => (Poll::Ready(None), true),
+            Ok(None) => {
+                self.timer.wake_after_poll_interval(cx.waker().clone());
+                (Poll::Pending, false)
+            }
+            Err(e) => {
+                let error = Output::ERROR {
+                    description: format!("拉取输出失败：{e}"),
+                };
+                (Poll::Ready(Some(error)), true)
+            }
+        };
+        if finished {
+            self.finished = true;
+            self.timer.stop();
+        }
+        poll
+    }
+
+    fn status(&self) -> &VmStatus {
+        self.inner.status()
+    }
+
+    async fn terminate(&mut self) -> Result<()> {
+        self.inner.terminate()
+    }
+
+    fn capabilities(&self) -> VmCapabilities {
+        self.inner.capabilities()
+    }
+}
+
+impl<L: VmLauncher + Send> AsyncVmLauncher for AsyncVmAdapter<L>
+where
+    L::Runtime: Send,
+{
+    type Runtime = AsyncVmAdapter<L::Runtime>;
+
+    async fn launch(self) -> Result<Self::Runtime> {
+        self.inner.launch().map(AsyncVmAdapter::new)
+    }
+
+    fn capabilities(&self) -> VmCapabilities {
+        self.inner.capabilities()
+    }
+}
+
+/// 唤醒时恢复指定线程
+/// * 🎯[`block_on`]中挂起、唤醒当前线程
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// 创建唤醒当前线程的[`Waker`]
+fn current_thread_waker() -> Waker {
+    Waker::from(Arc::new(ThreadWaker(std::thread::current())))
+}
+
+/// 在当前线程上阻塞，直到[`Future`]完成
+/// * 🚩最简单的执行器：未完成时挂起当前线程，直到被唤醒
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = current_thread_waker();
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => std::thread::park(),
+        }
+    }
+}
+
+/// 异步→同步 适配器
+/// * 🚩包装[`AsyncVmRuntime`]⇒[`VmRuntime`]；包装[`AsyncVmLauncher`]⇒[`VmLauncher`]
+/// * 🚩异步方法以[`block_on`]在当前线程上阻塞执行
+#[derive(Debug)]
+pub struct BlockingVmAdapter<T>(pub T);
+
+impl<T> BlockingVmAdapter<T> {
+    /// 解包，取回被包装的异步运行时/启动器
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<R: AsyncVmRuntime> VmRuntime for BlockingVmAdapter<R> {
+    fn input_cmd(&mut self, cmd: Cmd) -> Result<()> {
+        block_on(self.0.input_cmd(cmd))
+    }
+
+    /// * 🚩输出流已结束⇒报错
+    fn fetch_output(&mut self) -> Result<Output> {
+        block_on(poll_fn(|cx| self.0.poll_output(cx))).ok_or_else(|| anyhow!("输出流已结束"))
+    }
+
+    /// * 🚩仅轮询一次
+    fn try_fetch_output(&mut self) -> Result<Option<Output>> {
+        let waker = current_thread_waker();
+        let mut cx = Context::from_waker(&waker);
+        match self.0.poll_output(&mut cx) {
+            Poll::Ready(output) => Ok(output),
+            Poll::Pending => Ok(None),
+        }
+    }
+
+    /// * 🚩挂起当前线程直到被唤醒或超时，无需定时轮询
+    fn fetch_output_timeout(&mut self, timeout: Duration) -> Result<Option<Output>> {
+        let deadline = Instant::now() + timeout;
+        let waker = current_thread_waker();
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(output) = self.0.poll_output(&mut cx) {
+                return Ok(output);
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(None);
+            }
+            std::thread::park_timeout(deadline - now);
+        }
+    }
+
+    fn status(&self) -> &VmStatus {
+        self.0.status()
+    }
+
+    fn terminate(&mut self) -> Result<()> {
+        block_on(self.0.terminate())
+    }
+
+    fn capabilities(&self) -> VmCapabilities {
+        self.0.capabilities()
+    }
+}
+
+impl<L: AsyncVmLauncher> VmLauncher for BlockingVmAdapter<L> {
+    type Runtime = BlockingVmAdapter<L::Runtime>;
+
+    fn launch(self) -> Result<Self::Runtime> {
+        block_on(self.0.launch()).map(BlockingVmAdapter)
+    }
+
+    fn capabilities(&self) -> VmCapabilities {
+        self.0.capabilities()
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vm::test_script::tests::MockVm;
+    use std::{
+        collections::VecDeque,
+        sync::Mutex,
+        thread,
+    };
+
+    /// 拉取输出流中的下一个元素
+    fn next<S: Stream + Unpin>(stream: &mut S) -> Option<S::Item> {
+        block_on(poll_fn(|cx| Pin::new(&mut *stream).poll_next(cx)))
+    }
+
+    /// 测试用启动器
+    struct MockLauncher;
+
+    impl VmLauncher for MockLauncher {
+        type Runtime = MockVm;
+
+        fn launch(self) -> Result<MockVm> {
+            Ok(MockVm::new([(
+                1,
+                Output::INFO {
+                    message: "cycled".into(),
+                },
+            )]))
+        }
+    }
+
+    /// 测试用异步运行时
+    /// * 🚩输出由另一线程产生，并在产生时唤醒
+    #[derive(Default)]
+    struct ChannelVm {
+        /// 输出缓存与等待中的唤醒器
+        shared: Arc<Mutex<(VecDeque<Output>, Option<Waker>)>>,
+        /// 状态
+        status: Option<VmStatus>,
+    }
+
+    impl AsyncVmRuntime for ChannelVm {
+        async fn input_cmd(&mut self, cmd: Cmd) -> Result<()> {
+            let shared = self.shared.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(10));
+                let mut shared = shared.lock().unwrap();
+                shared.0.push_back(Output::IN {
+                    content: cmd.to_string(),
+                    narsese: None,
+                });
+                if let Some(waker) = shared.1.take() {
+                    waker.wake();
+                }
+            });
+            Ok(())
+        }
+
+        fn poll_output(&mut self, cx: &mut Context<'_>) -> Poll<Option<Output>> {
+            if self.is_terminated() {
+                return Poll::Ready(None);
+            }
+            let mut shared = self.shared.lock().unwrap();
+            match shared.0.pop_front() {
+                Some(output) => Poll::Ready(Some(output)),
+                None => {
+                    shared.1 = Some(cx.waker().clone());
+                    Poll::Pending
+                }
+            }
+        }
+
+        fn status(&self) -> &VmStatus {
+            self.status.as_ref().unwrap_or(&VmStatus::Running)
+        }
+
+        async fn terminate(&mut self) -> Result<()> {
+            self.status = Some(VmStatus::Terminated(Ok(())));
+            Ok(())
+        }
+    }
+
+    struct ChannelLauncher;
+
+    impl AsyncVmLauncher for ChannelLauncher {
+        type Runtime = ChannelVm;
+
+        async fn launch(self) -> Result<ChannelVm> {
+            Ok(ChannelVm::default())
+        }
+    }
+
+    /// 唤醒时发送消息
+    /// * 🎯不依赖具体时长，等待「最终被唤醒」
+    struct ChannelWaker(Mutex<mpsc::Sender<()>>);
+
+    impl Wake for ChannelWaker {
+        fn wake(self: Arc<Self>) {
+            let _ = self.0.lock().unwrap().send(());
+        }
+    }
+
+    /// 拉取总是出错的同步运行时
+    struct FailingVm;
+
+    impl VmRuntime for FailingVm {
+        fn input_cmd(&mut self, _: Cmd) -> Result<()> {
+            Ok(())
+        }
+
+        fn fetch_output(&mut self) -> Result<Output> {
+            Err(anyhow!("连接已断开"))
+        }
+
+        fn try_fetch_output(&mut self) -> Result<Option<Output>> {
+            Err(anyhow!("连接已断开"))
+        }
+
+        fn status(&self) -> &VmStatus {
+            &VmStatus::Running
+        }
+
+        fn terminate(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_sync_to_async() {
+        let mut vm = block_on(AsyncVmAdapter::new(MockLauncher).launch()).unwrap();
+        block_on(vm.input_cmd(Cmd::CYC(1))).unwrap();
+        let mut outputs = vm.outputs();
+        assert_eq!(
+            next(&mut outputs),
+            Some(Output::INFO {
+                message: "cycled".into()
+            })
+        );
+        // 暂无输出⇒挂起，并最终被唤醒
+        let (sender, receiver) = mpsc::channel();
+        let waker = Waker::from(Arc::new(ChannelWaker(Mutex::new(sender))));
+        let poll = vm.poll_output(&mut Context::from_waker(&waker));
+        assert_eq!(poll, Poll::Pending);
+        assert!(receiver.recv_timeout(Duration::from_secs(10)).is_ok());
+        // 输入后即可拉取
+        block_on(vm.input_cmd(Cmd::REM {
+            comment: "c".into(),
+        }))
+        .unwrap();
+        let output = block_on(vm.next_output()).unwrap();
+        assert!(output.is_type("IN"));
+        // 终止后输出流结束，后台线程随之停止
+        block_on(vm.terminate()).unwrap();
+        assert_eq!(block_on(vm.next_output()), None);
+        assert!(vm.timer.sender.is_none());
+    }
+
+    #[test]
+    fn test_sync_to_async_error() {
+        let mut vm = AsyncVmAdapter::new(FailingVm);
+        // 错误只输出一次，此后输出流结束
+        let output = block_on(vm.next_output()).unwrap();
+        assert!(output.is_type("ERROR"));
+        assert_eq!(block_on(vm.next_output()), None);
+        assert_eq!(block_on(vm.next_output()), None);
+    }
+
+    #[test]
+    fn test_async_to_sync() {
+        let mut vm = BlockingVmAdapter(ChannelLauncher).launch().unwrap();
+        assert_eq!(vm.try_fetch_output().unwrap(), None);
+        assert_eq!(
+            vm.fetch_output_timeout(Duration::from_millis(1)).unwrap(),
+            None
+        );
+        // 阻塞直到另一线程产生输出
+        vm.input_cmd(Cmd::CYC(1)).unwrap();
+        assert_eq!(
+            vm.fetch_output().unwrap(),
+            Output::IN {
+                content: "CYC 1".into(),
+                narsese: None
+            }
+        );
+        vm.input_cmd(Cmd::CYC(2)).unwrap();
+        let output = vm.fetch_output_timeout(Duration::from_secs(10)).unwrap();
+        assert!(output.is_some_and(|output| output.get_content() == "CYC 2"));
+        // 终止后输出流结束
+        vm.terminate().unwrap();
+        assert!(vm.is_terminated());
+        assert!(vm.fetch_output().is_err());
+    }
+}
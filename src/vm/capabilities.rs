@@ -0,0 +1,303 @@
+//! 定义「虚拟机能力描述」
+//! * 🎯让运行时/启动器声明所对接CIN的限制，并在发送前检查指令
+//!   * 📄ONA：复合词项最多有两个子项
+//!   * 📄PyNARS：尚未正式支持NAL 7~9
+//! * 📌默认「无限制」：未声明能力的虚拟机不应拒绝任何指令
+
+use crate::cmd::Cmd;
+use narsese::lexical::{Sentence as LexicalSentence, Term as LexicalTerm};
+use std::{collections::HashSet, error::Error, fmt::Display};
+
+/// 最高的NAL层级
+pub const MAX_NAL_LEVEL: u8 = 9;
+
+/// 虚拟机能力描述
+/// * 🚩各项为[`None`]⇒不作限制
+/// * 📌集合中的指令头为全大写；系词、连接词均为ASCII CommonNarsese写法
+///   * 📌外延集、内涵集以其左括号`{` `[`作为「连接词」
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VmCapabilities {
+    /// 支持的指令头
+    pub cmd_heads: Option<HashSet<String>>,
+
+    /// 支持的最高NAL层级
+    /// * 📄PyNARS：6
+    pub max_nal_level: u8,
+
+    /// 复合词项（含集合）组分个数的上限
+    /// * 📄ONA：2
+    pub max_compound_arity: Option<usize>,
+
+    /// 支持的系词
+    pub copulas: Option<HashSet<String>>,
+
+    /// 支持的连接词
+    pub connecters: Option<HashSet<String>>,
+
+    /// 是否支持保存/加载（[`Cmd::SAV`] [`Cmd::LOA`]）
+    pub save_load: bool,
+}
+
+/// 默认：无限制
+impl Default for VmCapabilities {
+    fn default() -> Self {
+        Self {
+            cmd_heads: None,
+            max_nal_level: MAX_NAL_LEVEL,
+            max_compound_arity: None,
+            copulas: None,
+            connecters: None,
+            save_load: true,
+        }
+    }
+}
+
+/// 指令不被支持的原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CapabilityError {
+    /// 不支持的指令头
+    UnsupportedCmd { head: String },
+    /// 不支持保存/加载
+    UnsupportedSaveLoad,
+    /// 不支持的系词
+    UnsupportedCopula(String),
+    /// 不支持的连接词
+    UnsupportedConnecter(String),
+    /// 复合词项组分过多
+    CompoundTooLarge {
+        connecter: String,
+        arity: usize,
+        max: usize,
+    },
+    /// NAL层级过高
+    NalLevelTooHigh { required: u8, max: u8 },
+}
+
+impl Display for CapabilityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CapabilityError::UnsupportedCmd { head } => write!(f, "不支持的指令「{head}」"),
+            CapabilityError::UnsupportedSaveLoad => write!(f, "不支持保存/加载"),
+            CapabilityError::UnsupportedCopula(copula) => write!(f, "不支持的系词「{copula}」"),
+            CapabilityError::UnsupportedConnecter(connecter) => {
+                write!(f, "不支持的连接词「{connecter}」")
+            }
+            CapabilityError::CompoundTooLarge {
+                connecter,
+                arity,
+                max,
+            } => write!(f, "复合词项「{connecter}」有{arity}个组分，超出上限{max}个"),
+            CapabilityError::NalLevelTooHigh { required, max } => {
+                write!(f, "需要NAL-{required}，但最高仅支持NAL-{max}")
+            }
+        }
+    }
+}
+
+impl Error for CapabilityError {}
+
+impl VmCapabilities {
+    /// 是否支持某指令头
+    /// * 🚩不区分大小写
+    pub fn supports_head(&self, head: &str) -> bool {
+        match &self.cmd_heads {
+            Some(heads) => heads.contains(&head.to_uppercase()),
+            None => true,
+        }
+    }
+
+    /// 检查词项
+    /// * 🚩逐层检查系词、连接词、组分个数
+    fn validate_term(&self, term: &LexicalTerm) -> Result<(), CapabilityError> {
+        // 按「连接词+组分」检查
+        let check_compound = |connecter: &str, terms: &[LexicalTerm]| {
+            if let Some(connecters) = &self.connecters {
+                if !connecters.contains(connecter) {
+                    return Err(CapabilityError::UnsupportedConnecter(connecter.into()));
+                }
+            }
+            if let Some(max) = self.max_compound_arity {
+                if terms.len() > max {
+                    return Err(CapabilityError::CompoundTooLarge {
+                        connecter: connecter.into(),
+                        arity: terms.len(),
+                        max,
+                    });
+                }
+            }
+            terms.iter().try_for_each(|term| self.validate_term(term))
+        };
+        match term {
+            LexicalTerm::Atom { .. } => Ok(()),
+            LexicalTerm::Compound { connecter, terms } => check_compound(connecter, terms),
+            LexicalTerm::Set {
+                left_bracket,
+                terms,
+                ..
+            } => check_compound(left_bracket, terms),
+            LexicalTerm::Statement {
+                copula,
+                subject,
+                predicate,
+            } => {
+                if let Some(copulas) = &self.copulas {
+                    if !copulas.contains(copula) {
+                        return Err(CapabilityError::UnsupportedCopula(copula.clone()));
+                    }
+                }
+                self.validate_term(subject)?;
+                self.validate_term(predicate)
+            }
+        }
+    }
+
+    /// 检查语句
+    /// * 🚩先检查NAL层级，再检查词项
+    fn validate_sentence(&self, sentence: &LexicalSentence) -> Result<(), CapabilityError> {
+        let required = nal_level_of_sentence(sentence);
+        if required > self.max_nal_level {
+            return Err(CapabilityError::NalLevelTooHigh {
+                required,
+                max: self.max_nal_level,
+            });
+        }
+        self.validate_term(&sentence.term)
+    }
+}
+
+/// 系词所需的NAL层级
+fn nal_level_of_copula(copula: &str) -> u8 {
+    match copula {
+        "-->" => 1,
+        "<->" | "{--" | "--]" | "{-]" => 2,
+        "==>" | "<=>" => 5,
+        // 时序系词
+        _ => 7,
+    }
+}
+
+/// 连接词（含集合左括号）所需的NAL层级
+fn nal_level_of_connecter(connecter: &str) -> u8 {
+    match connecter {
+        "{" | "[" | "&" | "|" | "-" | "~" => 3,
+        "*" | "/" | r"\" => 4,
+        "--" | "&&" | "||" => 5,
+        // 时序连接词
+        _ => 7,
+    }
+}
+
+/// 词项所需的NAL层级
+/// * 🚩取各组分的最大值
+fn nal_level_of_term(term: &LexicalTerm) -> u8 {
+    /// 组分所需层级的最大值
+    fn max_of(terms: &[LexicalTerm]) -> u8 {
+        terms.iter().map(nal_level_of_term).max().unwrap_or(1)
+    }
+    match term {
+        LexicalTerm::Atom { prefix, .. } => match prefix.as_str() {
+            // 变量
+            "$" | "#" | "?" => 6,
+            // 操作符
+            "^" => 8,
+            _ => 1,
+        },
+        LexicalTerm::Compound { connecter, terms } => {
+            nal_level_of_connecter(connecter).max(max_of(terms))
+        }
+        LexicalTerm::Set {
+            left_bracket,
+            terms,
+            ..
+        } => nal_level_of_connecter(left_bracket).max(max_of(terms)),
+        LexicalTerm::Statement {
+            copula,
+            subject,
+            predicate,
+        } => nal_level_of_copula(copula)
+            .max(nal_level_of_term(subject))
+            .max(nal_level_of_term(predicate)),
+    }
+}
+
+/// 语句所需的NAL层级
+/// * 🚩词项+标点（目标、请求⇒NAL-8）+时间戳（非永恒⇒NAL-7）
+fn nal_level_of_sentence(sentence: &LexicalSentence) -> u8 {
+    let punctuation = match sentence.punctuation.as_str() {
+        "!" | "@" => 8,
+        _ => 1,
+    };
+    let stamp = match sentence.stamp.trim().is_empty() {
+        true => 1,
+        false => 7,
+    };
+    nal_level_of_term(&sentence.term)
+        .max(punctuation)
+        .max(stamp)
+}
+
+impl Cmd {
+    /// 检查指令是否被某虚拟机支持
+    /// * 🎯在发送前说明「为何会被拒绝」
+    /// * 🚩检查指令头、保存/加载；对[`Cmd::NSE`]还会检查其中的Narsese
+    pub fn validate(&self, capabilities: &VmCapabilities) -> Result<(), CapabilityError> {
+        let head = self.head();
+        if !capabilities.supports_head(head) {
+            return Err(CapabilityError::UnsupportedCmd { head: head.into() });
+        }
+        match self {
+            Cmd::SAV { .. } | Cmd::LOA { .. } if !capabilities.save_load => {
+                Err(CapabilityError::UnsupportedSaveLoad)
+            }
+            Cmd::NSE(task) => capabilities.validate_sentence(&task.sentence),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nar_dev_utils::asserts;
+
+    #[test]
+    fn test_validate() {
+        let cmd = |line: &str| Cmd::parse(line).unwrap();
+        // 默认无限制
+        let unrestricted = VmCapabilities::default();
+        for line in [
+            "SAV memory a.json",
+            "NSE <(&/, <a --> b>, <(*, {SELF}, x) --> ^left>) =/> <c --> d>>! :|:",
+            "HEAD tail",
+        ] {
+            assert_eq!(cmd(line).validate(&unrestricted), Ok(()));
+        }
+        // 受限：仿ONA、PyNARS
+        let restricted = VmCapabilities {
+            cmd_heads: Some(["NSE", "CYC", "LOA"].map(String::from).into()),
+            max_nal_level: 6,
+            max_compound_arity: Some(2),
+            copulas: Some(["-->", "==>"].map(String::from).into()),
+            save_load: false,
+            ..Default::default()
+        };
+        let validate = |line: &str| cmd(line).validate(&restricted);
+        asserts! {
+            validate("NSE <(*, a, b) --> c>.") => Ok(())
+            validate("CYC 1") => Ok(())
+            validate("VOL 1") => Err(CapabilityError::UnsupportedCmd { head: "VOL".into() })
+            validate("LOA memory a.json") => Err(CapabilityError::UnsupportedSaveLoad)
+            validate("NSE <a <-> b>.") => Err(CapabilityError::UnsupportedCopula("<->".into()))
+            validate("NSE <(&&, a, b, c) ==> d>.") => Err(CapabilityError::CompoundTooLarge {
+                connecter: "&&".into(),
+                arity: 3,
+                max: 2,
+            })
+            validate("NSE <a --> b>. :|:") => Err(CapabilityError::NalLevelTooHigh { required: 7, max: 6 })
+            validate("NSE <a --> ^left>!") => Err(CapabilityError::NalLevelTooHigh { required: 8, max: 6 })
+        }
+        // 错误信息可展示
+        println!("{}", validate("NSE <a --> b>. :|:").unwrap_err());
+    }
+}
@@ -9,4 +9,16 @@ nar_dev_utils::mod_and_pub_use! {
     structs
     // 特征
     traits
+    // 异步特征
+    asynchronous
+    // 输出迭代器
+    outputs
+    // 能力描述
+    capabilities
+    // 测试脚本
+    test_script
+    // 加盖时间戳的运行时
+    stamped
+    // 操作的注册与自动应答
+    operation
 }
@@ -0,0 +1,400 @@
+//! 提供「NARS操作」的注册与自动应答
+//! * 🎯CIN输出[`Output::EXE`]时，自动调用相应的Rust处理函数，并回馈操作事件
+//!   * 📌而非由各智能体手动匹配操作名、执行动作、输入`<(*, {SELF}) --> ^left>. :|:`
+//! * 🚩由使用者向[`OperationRegistry`]注册「操作名+参数个数+处理函数」
+//! * 🚩由[`OperationRuntime`]包装运行时
+//!   * 启动时：为每个操作输入[`Cmd::REG`]
+//!   * 拉取到[`Output::EXE`]时：校验参数、调用处理函数、输入回馈的[`Cmd::NSE`]
+
+use super::{VmCapabilities, VmRuntime, VmStatus};
+use crate::{
+    cmd::Cmd,
+    output::{Operation, Output},
+};
+use anyhow::{anyhow, Result};
+use narsese::lexical::{Sentence as LexicalSentence, Task as LexicalTask, Term as LexicalTerm};
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Duration,
+};
+
+/// 操作的处理函数
+/// * 🚩传入（参数已校验的）操作，返回需额外输入的指令
+///   * 📄如`<{SELF} --> [good]>. :|:`形式的奖励
+/// * 📌报错⇒作为[`Output::ERROR`]输出
+pub type OperationHandler = Box<dyn FnMut(&Operation) -> Result<Vec<Cmd>> + Send>;
+
+/// 已注册的操作
+struct RegisteredOperation {
+    /// 参数个数
+    arity: usize,
+    /// 处理函数
+    handler: OperationHandler,
+}
+
+/// 操作注册表
+/// * 🎯存储「操作名→参数个数+处理函数」的映射
+/// * 📌操作名不带尖号
+pub struct OperationRegistry {
+    /// 处理函数映射表
+    operations: HashMap<String, RegisteredOperation>,
+    /// 是否回馈操作事件
+    /// * 🚩默认回馈
+    feedback: bool,
+}
+
+impl Default for OperationRegistry {
+    fn default() -> Self {
+        Self {
+            operations: HashMap::new(),
+            feedback: true,
+        }
+    }
+}
+
+impl std::fmt::Debug for OperationRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OperationRegistry")
+            .field("operations", &self.names())
+            .field("feedback", &self.feedback)
+            .finish()
+    }
+}
+
+/// 去除操作名前的尖号
+/// * 🎯各方法的操作名均可带尖号：`^left`与`left`等同
+fn strip_caret(name: &str) -> &str {
+    name.strip_prefix('^').unwrap_or(name)
+}
+
+impl OperationRegistry {
+    /// 构造函数
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册操作
+    /// * 🚩操作名可带尖号，注册时去除
+    /// * 🚩同名操作⇒覆盖
+    pub fn register(
+        &mut self,
+        name: &str,
+        arity: usize,
+        handler: impl FnMut(&Operation) -> Result<Vec<Cmd>> + Send + 'static,
+    ) -> &mut Self {
+        self.operations.insert(
+            strip_caret(name).to_owned(),
+            RegisteredOperation {
+                arity,
+                handler: Box::new(handler),
+            },
+        );
+        self
+    }
+
+    /// 设置是否回馈操作事件
+    /// * 🎯部分CIN会自行记录操作事件，无需重复输入
+    pub fn set_feedback(&mut self, feedback: bool) -> &mut Self {
+        self.feedback = feedback;
+        self
+    }
+
+    /// 操作是否已注册
+    /// * 🚩操作名可带尖号
+    pub fn contains(&self, name: &str) -> bool {
+        self.operations.contains_key(strip_caret(name))
+    }
+
+    /// 获取已注册操作的参数个数
+    /// * 🚩操作名可带尖号
+    pub fn arity(&self, name: &str) -> Option<usize> {
+        self.operations
+            .get(strip_caret(name))
+            .map(|operation| operation.arity)
+    }
+
+    /// 获取所有已注册的操作名
+    /// * 🚩按字典序排列，以便输入顺序确定
+    pub fn names(&self) -> Vec<&str> {
+        let mut names = self.operations.keys().map(String::as_str).collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    /// 生成注册所有操作的指令
+    pub fn reg_cmds(&self) -> Vec<Cmd> {
+        self.names()
+            .into_iter()
+            .map(|name| Cmd::REG { name: name.into() })
+            .collect()
+    }
+
+    /// 生成回馈操作事件的指令
+    /// * 📄`NSE <(*, {SELF}) --> ^left>. :|:`
+    /// * 🚩无参操作⇒以`{SELF}`为唯一参数，避免生成空乘积`(*)`
+    pub fn feedback_cmd(operation: &Operation) -> Cmd {
+        let term = match operation.no_params() {
+            true => {
+                let self_term = LexicalTerm::new_set("{", vec![LexicalTerm::new_atom("", "SELF")], "}");
+                Operation::new(&operation.operator_name, [self_term]).to_term()
+            }
+            false => operation.to_term(),
+        };
+        Cmd::NSE(LexicalTask {
+            budget: vec![],
+            sentence: LexicalSentence {
+                term,
+                punctuation: ".".into(),
+                stamp: ":|:".into(),
+                truth: vec![],
+            },
+        })
+    }
+
+    /// 分派操作
+    /// * 🚩未注册⇒[`None`]
+    /// * 🚩参数个数不符⇒报错，不调用处理函数
+    /// * 🚩返回需输入的指令：（若回馈）操作事件在前，处理函数返回的指令在后
+    pub fn dispatch(&mut self, operation: &Operation) -> Option<Result<Vec<Cmd>>> {
+        let feedback = self.feedback;
+        let registered = self.operations.get_mut(strip_caret(&operation.operator_name))?;
+        Some(Self::call(registered, feedback, operation))
+    }
+
+    /// 校验并调用处理函数
+    fn call(
+        registered: &mut RegisteredOperation,
+        feedback: bool,
+        operation: &Operation,
+    ) -> Result<Vec<Cmd>> {
+        if operation.params.len() != registered.arity {
+            return Err(anyhow!(
+                "操作参数个数不符：应有{}个，实有{}个 @ {operation}",
+                registered.arity,
+                operation.params.len(),
+            ));
+        }
+        let extra = (registered.handler)(operation)?;
+        let mut cmds = Vec::with_capacity(extra.len() + 1);
+        if feedback {
+            cmds.push(Self::feedback_cmd(operation));
+        }
+        cmds.extend(extra);
+        Ok(cmds)
+    }
+}
+
+/// 自动应答操作的运行时
+/// * 🚩拉取输出时，将已注册操作的[`Output::EXE`]分派给处理函数
+///   * 📌[`Output::EXE`]本身仍照常输出
+///   * 📌分派失败（参数不符、处理函数报错、回馈输入失败）⇒其后追加一个[`Output::ERROR`]
+/// * 📌未注册的操作不受影响
+#[derive(Debug)]
+pub struct OperationRuntime<R: VmRuntime> {
+    /// 被包装的运行时
+    inner: R,
+    /// 操作注册表
+    registry: OperationRegistry,
+    /// 待输出的错误
+    pending: VecDeque<Output>,
+}
+
+impl<R: VmRuntime> OperationRuntime<R> {
+    /// 构造函数
+    /// * 🚩为每个已注册的操作输入[`Cmd::REG`]
+    pub fn new(mut inner: R, registry: OperationRegistry) -> Result<Self> {
+        for cmd in registry.reg_cmds() {
+            inner.input_cmd(cmd)?;
+        }
+        Ok(Self {
+            inner,
+            registry,
+            pending: VecDeque::new(),
+        })
+    }
+
+    /// 获取操作注册表
+    pub fn registry(&self) -> &OperationRegistry {
+        &self.registry
+    }
+
+    /// 获取被包装的运行时
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+
+    /// 获取被包装的运行时（可变）
+    /// * ⚠️直接从中拉取的输出不会被分派
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// 解包，取回被包装的运行时
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// 处理拉取到的输出
+    /// * 🚩已注册的操作⇒分派，并输入回馈
+    fn handle(&mut self, output: &Output) {
+        let Some(operation) = output.get_operation() else {
+            return;
+        };
+        let result = match self.registry.dispatch(operation) {
+            None => return,
+            Some(result) => result.and_then(|cmds| {
+                cmds.into_iter()
+                    .try_for_each(|cmd| self.inner.input_cmd(cmd))
+            }),
+        };
+        if let Err(e) = result {
+            self.pending.push_back(Output::ERROR {
+                description: format!("操作「{operation}」处理失败：{e}"),
+            });
+        }
+    }
+}
+
+impl<R: VmRuntime> VmRuntime for OperationRuntime<R> {
+    fn input_cmd(&mut self, cmd: Cmd) -> Result<()> {
+        self.inner.input_cmd(cmd)
+    }
+
+    fn fetch_output(&mut self) -> Result<Output> {
+        if let Some(output) = self.pending.pop_front() {
+            return Ok(output);
+        }
+        let output = self.inner.fetch_output()?;
+        self.handle(&output);
+        Ok(output)
+    }
+
+    fn try_fetch_output(&mut self) -> Result<Option<Output>> {
+        if let Some(output) = self.pending.pop_front() {
+            return Ok(Some(output));
+        }
+        let output = self.inner.try_fetch_output()?;
+        if let Some(output) = &output {
+            self.handle(output);
+        }
+        Ok(output)
+    }
+
+    fn fetch_output_timeout(&mut self, timeout: Duration) -> Result<Option<Output>> {
+        if let Some(output) = self.pending.pop_front() {
+            return Ok(Some(output));
+        }
+        let output = self.inner.fetch_output_timeout(timeout)?;
+        if let Some(output) = &output {
+            self.handle(output);
+        }
+        Ok(output)
+    }
+
+    fn status(&self) -> &VmStatus {
+        self.inner.status()
+    }
+
+    fn terminate(&mut self) -> Result<()> {
+        self.inner.terminate()
+    }
+
+    fn capabilities(&self) -> VmCapabilities {
+        self.inner.capabilities()
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        operation,
+        vm::{test_script::tests::MockVm, VmRuntimeExt},
+    };
+    use nar_dev_utils::asserts;
+    use narsese::lexical_nse_task;
+    use std::sync::{Arc, Mutex};
+
+    /// 拉取所有输出，呈现为纯文本
+    fn drain(vm: &mut impl VmRuntime) -> Vec<String> {
+        vm.drain_outputs()
+            .map(|output| output.unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_registry() {
+        let mut registry = OperationRegistry::new();
+        registry
+            .register("^left", 1, |_| Ok(vec![]))
+            .register("right", 1, |_| Ok(vec![]));
+        assert_eq!(registry.names(), ["left", "right"]);
+        asserts! {
+            registry.arity("left") => Some(1)
+            registry.arity("^right") => Some(1)
+            registry.arity("up") => None
+            registry.contains("^left") => true
+            registry.contains("right") => true
+            registry.contains("^up") => false
+        }
+        assert_eq!(
+            OperationRegistry::feedback_cmd(&operation!("left" => "{SELF}")).to_string(),
+            "NSE <(*, {SELF}) --> ^left>. :|:"
+        );
+        // 无参操作：补上`{SELF}`
+        assert_eq!(
+            OperationRegistry::feedback_cmd(&operation!("left")).to_string(),
+            "NSE <(*, {SELF}) --> ^left>. :|:"
+        );
+        // 参数个数不符
+        assert!(registry
+            .dispatch(&operation!("left" => "{SELF}" "x"))
+            .unwrap()
+            .is_err());
+        // 未注册
+        assert!(registry.dispatch(&operation!("up")).is_none());
+    }
+
+    #[test]
+    fn test_runtime() {
+        let exe = |operation: Operation| Output::EXE {
+            content_raw: String::new(),
+            operation,
+        };
+        let calls = Arc::new(Mutex::new(vec![]));
+        let mut registry = OperationRegistry::new();
+        let calls_left = calls.clone();
+        registry
+            .register("left", 1, move |operation| {
+                calls_left.lock().unwrap().push(operation.to_string());
+                Ok(vec![Cmd::NSE(lexical_nse_task!(<{SELF} --> [good]>. :|:))])
+            })
+            .register("fail", 0, |_| Err(anyhow!("故障")));
+        let vm = MockVm::new([
+            (1, exe(operation!("left" => "{SELF}"))),
+            (1, exe(operation!("left"))),
+            (1, exe(operation!("fail"))),
+            (1, exe(operation!("up"))),
+        ]);
+        let mut vm = OperationRuntime::new(vm, registry).unwrap();
+        // 启动时注册
+        assert_eq!(drain(&mut vm), ["IN: // REG fail", "IN: // REG left"]);
+        vm.input_cmd(Cmd::CYC(1)).unwrap();
+        assert_eq!(
+            drain(&mut vm),
+            [
+                "EXE: <(*, {SELF}) --> ^left> // ",
+                "EXE: <(*) --> ^left> // ",
+                "ERROR: 操作「<(*) --> ^left>」处理失败：操作参数个数不符：应有1个，实有0个 @ <(*) --> ^left>",
+                "EXE: <(*) --> ^fail> // ",
+                "ERROR: 操作「<(*) --> ^fail>」处理失败：故障",
+                "EXE: <(*) --> ^up> // ",
+                // 回馈
+                "IN: // NSE <(*, {SELF}) --> ^left>. :|:",
+                "IN: // NSE <{SELF} --> [good]>. :|:",
+            ]
+        );
+        assert_eq!(*calls.lock().unwrap(), ["<(*, {SELF}) --> ^left>"]);
+    }
+}
@@ -0,0 +1,176 @@
+//! 为「虚拟机运行时」提供拉取输出的迭代器
+//! * 🎯取代各处的`while let Ok(Some(output)) = vm.try_fetch_output()`
+//! * 📌拉取出错时，作为迭代器的元素返回，而非静默结束循环
+//!   * 🚩返回错误后，迭代器随即结束
+
+use super::VmRuntime;
+use crate::output::Output;
+use anyhow::Result;
+use std::iter::FusedIterator;
+
+/// 非阻塞的输出迭代器
+/// * 🚩由[`VmRuntimeExt::drain_outputs`]创建
+/// * 🚩暂无输出⇒结束
+#[derive(Debug)]
+pub struct DrainOutputs<'a, R: VmRuntime + ?Sized> {
+    /// 所属的运行时
+    vm: &'a mut R,
+    /// 是否已结束
+    done: bool,
+}
+
+impl<R: VmRuntime + ?Sized> Iterator for DrainOutputs<'_, R> {
+    type Item = Result<Output>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let result = self.vm.try_fetch_output().transpose();
+        self.done = !matches!(result, Some(Ok(..)));
+        result
+    }
+}
+
+impl<R: VmRuntime + ?Sized> FusedIterator for DrainOutputs<'_, R> {}
+
+/// 阻塞的输出迭代器
+/// * 🚩由[`VmRuntimeExt::outputs`]创建
+/// * ⚠️暂无输出时阻塞调用者；仅在拉取出错时结束
+#[derive(Debug)]
+pub struct BlockingOutputs<'a, R: VmRuntime + ?Sized> {
+    /// 所属的运行时
+    vm: &'a mut R,
+    /// 是否已结束
+    done: bool,
+}
+
+impl<R: VmRuntime + ?Sized> Iterator for BlockingOutputs<'_, R> {
+    type Item = Result<Output>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let result = self.vm.fetch_output();
+        self.done = result.is_err();
+        Some(result)
+    }
+}
+
+impl<R: VmRuntime + ?Sized> FusedIterator for BlockingOutputs<'_, R> {}
+
+/// 对[`VmRuntime`]的扩展
+/// * 🚩自动为所有运行时实现
+pub trait VmRuntimeExt: VmRuntime {
+    /// 拉取当前所有的输出（非阻塞）
+    /// * 🚩基于[`VmRuntime::try_fetch_output`]：暂无输出⇒结束
+    fn drain_outputs(&mut self) -> DrainOutputs<'_, Self> {
+        DrainOutputs {
+            vm: self,
+            done: false,
+        }
+    }
+
+    /// 逐个拉取输出（阻塞）
+    /// * 🚩基于[`VmRuntime::fetch_output`]：暂无输出⇒等待
+    fn outputs(&mut self) -> BlockingOutputs<'_, Self> {
+        BlockingOutputs {
+            vm: self,
+            done: false,
+        }
+    }
+
+    /// 收集输出，直到出现满足条件的输出，或已收集`limit`个输出
+    /// * 🎯如「等待回答」：`vm.collect_until(|o| o.is_type("ANSWER"), 100)`
+    /// * 🚩阻塞拉取；满足条件的输出亦被收集，且总在最后
+    /// * 🚩拉取出错⇒报错
+    fn collect_until(
+        &mut self,
+        mut predicate: impl FnMut(&Output) -> bool,
+        limit: usize,
+    ) -> Result<Vec<Output>> {
+        let mut outputs = vec![];
+        for output in self.outputs().take(limit) {
+            let output = output?;
+            let found = predicate(&output);
+            outputs.push(output);
+            if found {
+                break;
+            }
+        }
+        Ok(outputs)
+    }
+}
+
+impl<R: VmRuntime + ?Sized> VmRuntimeExt for R {}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cmd::Cmd,
+        output::type_names::{ANSWER, IN},
+        vm::test_script::tests::MockVm,
+    };
+    use nar_dev_utils::asserts;
+
+    /// 样例输出
+    fn answer() -> Output {
+        Output::ANSWER {
+            content_raw: "answer".into(),
+            narsese: None,
+        }
+    }
+
+    #[test]
+    fn test_drain_outputs() {
+        let mut vm = MockVm::new([(1, answer())]);
+        vm.input_cmd(Cmd::CYC(1)).unwrap();
+        vm.input_cmd(Cmd::REM {
+            comment: "c".into(),
+        })
+        .unwrap();
+        let outputs = vm.drain_outputs().collect::<Result<Vec<_>>>().unwrap();
+        asserts! {
+            outputs.len() => 2,
+            outputs[0] => answer(),
+            outputs[1].is_type(IN) => true
+            vm.drain_outputs().count() => 0,
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        // 阻塞拉取出错⇒作为元素返回，随后结束
+        let mut vm = MockVm::new([]);
+        let mut outputs = vm.outputs();
+        assert!(outputs.next().unwrap().is_err());
+        assert!(outputs.next().is_none());
+        assert!(vm.collect_until(|_| true, 10).is_err());
+    }
+
+    #[test]
+    fn test_collect_until() {
+        let mut vm = MockVm::new([(1, answer()), (1, answer())]);
+        for comment in ["a", "b"] {
+            vm.input_cmd(Cmd::REM {
+                comment: comment.into(),
+            })
+            .unwrap();
+        }
+        vm.input_cmd(Cmd::CYC(1)).unwrap();
+        // 上限
+        let outputs = vm.collect_until(|o| o.is_type(ANSWER), 1).unwrap();
+        assert_eq!(outputs.len(), 1);
+        // 满足条件的输出在最后
+        let outputs = vm.collect_until(|o| o.is_type(ANSWER), 10).unwrap();
+        asserts! {
+            outputs.len() => 2,
+            outputs[0].is_type(IN) => true
+            outputs[1] => answer(),
+        }
+        assert_eq!(vm.drain_outputs().count(), 1);
+    }
+}
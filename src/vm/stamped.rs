@@ -0,0 +1,172 @@
+//! 为输出加盖「时间戳」的运行时包装
+//! * 🎯拉取输出时，附上序号、到达时间、周期数与来源
+//! * 📌不改变被包装的运行时：输入、状态、终止均原样转发
+
+use super::{VmCapabilities, VmRuntime, VmStatus};
+use crate::{
+    cmd::Cmd,
+    output::{Output, OutputRecord},
+};
+use anyhow::Result;
+use std::time::Duration;
+
+/// 加盖时间戳的运行时
+/// * 🚩拉取输出时生成[`OutputRecord`]
+///   * 序号：从`0`开始，每拉取一个输出递增
+///   * 周期数：累计经此输入的[`Cmd::CYC`]
+/// * 📌作为[`VmRuntime`]使用时，仍会消耗序号
+#[derive(Debug)]
+pub struct StampedRuntime<R: VmRuntime> {
+    /// 被包装的运行时
+    inner: R,
+    /// 来源
+    source: String,
+    /// 下一个序号
+    next_seq: u64,
+    /// 累计输入的推理周期数
+    cycle: usize,
+}
+
+impl<R: VmRuntime> StampedRuntime<R> {
+    /// 构造函数
+    pub fn new(inner: R, source: impl Into<String>) -> Self {
+        Self {
+            inner,
+            source: source.into(),
+            next_seq: 0,
+            cycle: 0,
+        }
+    }
+
+    /// 获取来源
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// 获取累计输入的推理周期数
+    pub fn cycle(&self) -> usize {
+        self.cycle
+    }
+
+    /// 获取被包装的运行时
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+
+    /// 获取被包装的运行时（可变）
+    /// * ⚠️直接从中拉取的输出不会被记录
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// 解包，取回被包装的运行时
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// 为输出加盖时间戳
+    fn stamp(&mut self, output: Output) -> OutputRecord {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        OutputRecord::new(seq, Some(self.cycle), self.source.clone(), output)
+    }
+
+    /// 拉取一个输出，并加盖时间戳
+    /// * ⚠️若暂时没输出，则会阻塞调用者
+    pub fn fetch_record(&mut self) -> Result<OutputRecord> {
+        let output = self.inner.fetch_output()?;
+        Ok(self.stamp(output))
+    }
+
+    /// 尝试拉取一个输出，并加盖时间戳
+    pub fn try_fetch_record(&mut self) -> Result<Option<OutputRecord>> {
+        let output = self.inner.try_fetch_output()?;
+        Ok(output.map(|output| self.stamp(output)))
+    }
+
+    /// 拉取一个输出并加盖时间戳，至多等待指定的时长
+    /// * 🔗[`VmRuntime::fetch_output_timeout`]
+    pub fn fetch_record_timeout(&mut self, timeout: Duration) -> Result<Option<OutputRecord>> {
+        let output = self.inner.fetch_output_timeout(timeout)?;
+        Ok(output.map(|output| self.stamp(output)))
+    }
+}
+
+impl<R: VmRuntime> VmRuntime for StampedRuntime<R> {
+    fn input_cmd(&mut self, cmd: Cmd) -> Result<()> {
+        let steps = match cmd {
+            Cmd::CYC(steps) => steps,
+            _ => 0,
+        };
+        self.inner.input_cmd(cmd)?;
+        self.cycle += steps;
+        Ok(())
+    }
+
+    fn fetch_output(&mut self) -> Result<Output> {
+        self.fetch_record().map(Output::from)
+    }
+
+    fn try_fetch_output(&mut self) -> Result<Option<Output>> {
+        Ok(self.try_fetch_record()?.map(Output::from))
+    }
+
+    fn fetch_output_timeout(&mut self, timeout: Duration) -> Result<Option<Output>> {
+        Ok(self.fetch_record_timeout(timeout)?.map(Output::from))
+    }
+
+    fn status(&self) -> &VmStatus {
+        self.inner.status()
+    }
+
+    fn terminate(&mut self) -> Result<()> {
+        self.inner.terminate()
+    }
+
+    fn capabilities(&self) -> VmCapabilities {
+        self.inner.capabilities()
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vm::test_script::tests::MockVm;
+    use nar_dev_utils::asserts;
+
+    #[test]
+    fn test_stamp() {
+        let out = |content: &str| Output::OUT {
+            content_raw: content.into(),
+            narsese: None,
+        };
+        let mut vm = StampedRuntime::new(MockVm::new([(2, out("a")), (3, out("b"))]), "mock");
+        vm.input_cmd(Cmd::CYC(2)).unwrap();
+        let a = vm.fetch_record().unwrap();
+        vm.input_cmd(Cmd::CYC(1)).unwrap();
+        let b = vm.try_fetch_record().unwrap().unwrap();
+        vm.input_cmd(Cmd::REM {
+            comment: "c".into(),
+        })
+        .unwrap();
+        let c = vm.fetch_output().unwrap();
+        asserts! {
+            a.seq => 0,
+            a.cycle => Some(2),
+            a.source => "mock",
+            a.output => out("a"),
+            b.seq => 1,
+            b.cycle => Some(3),
+            b.output => out("b"),
+            b.wall_time >= a.wall_time => true
+            c.is_type("IN") => true
+            // 作为运行时拉取输出时，仍消耗序号
+            vm.try_fetch_record().unwrap() => None,
+            vm.next_seq => 3,
+            vm.cycle() => 3,
+        }
+        vm.terminate().unwrap();
+        assert!(vm.is_terminated());
+    }
+}
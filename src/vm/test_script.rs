@@ -0,0 +1,623 @@
+//! 定义「NAVM测试脚本」及其运行器
+//! * 🎯以脚本而非临时代码测试各CIN的推理行为
+//! * 📌测试脚本：在[「NAVM脚本」](crate::cmd::CmdScript)语法之上，穿插「输出预期」行
+//!   * `EXPECT [NO] <输出模式> [WITHIN <周期数>]`
+//!     * `NO`：预期「不出现」
+//!     * 输出模式：见[`OutputMatcher`]，如`ANSWER <A --> C> c>=0.5`
+//!     * `WITHIN`：至多额外推理多少个周期
+//!   * 📄`EXPECT ANSWER <A --> C> WITHIN 100`
+//!   * 📄`EXPECT EXE ^left`
+//!   * 📄`EXPECT NO ERROR`
+//! * 🚩「输出预期」作为[扩展指令](crate::cmd::TypedCmd)解析
+//!   * 📌亦可与[预处理器](crate::cmd::Preprocessor)结合：在其解析选项中注册[`Expectation`]即可
+
+use super::{VmRuntime, VmRuntimeExt};
+use crate::{
+    cmd::{
+        get_cmd_param_usize, Cmd, CmdRegistry, CmdScript, ParseOptions, ParseResult,
+        ScriptCmd, ScriptError, TypedCmd,
+    },
+    output::{split_words, type_names::TERMINATED, Output, OutputMatcher},
+};
+use anyhow::Result;
+use std::{fmt::Display, time::Duration};
+
+/// 「输出预期」的指令头
+pub const EXPECT_HEAD: &str = "EXPECT";
+
+/// 「预期不出现」的关键字
+const NO_KEYWORD: &str = "NO";
+
+/// 「周期数上限」的关键字
+const WITHIN_KEYWORD: &str = "WITHIN";
+
+/// 输出预期
+/// * 🎯描述「应该/不应该出现怎样的输出」
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expectation {
+    /// 是否为「预期不出现」
+    pub negated: bool,
+    /// 输出匹配器
+    pub matcher: OutputMatcher,
+    /// 至多额外推理的周期数
+    /// * 🚩[`None`]
+    ///   * 「预期出现」⇒只检查已有输出
+    ///   * 「预期不出现」⇒检查直到脚本结束的所有输出
+    pub within: Option<usize>,
+}
+
+impl Expectation {
+    /// 判断输出是否符合此预期（不考虑`NO`）
+    pub fn matches(&self, output: &Output) -> bool {
+        self.matcher.is_match(output)
+    }
+}
+
+impl TypedCmd for Expectation {
+    const HEAD: &'static str = EXPECT_HEAD;
+
+    fn parse_tail(tail: &str) -> ParseResult<Self> {
+        let words = split_words(tail);
+        // `WITHIN <周期数>`：位于末尾
+        let mut end = tail.len();
+        let mut within = None;
+        if let [.., (keyword, keyword_span), (num, num_span)] = &words[..] {
+            if keyword.eq_ignore_ascii_case(WITHIN_KEYWORD) {
+                within = Some(get_cmd_param_usize(num).map_err(|e| e.offset(num_span.start))?);
+                end = keyword_span.start;
+            }
+        }
+        // `NO`：位于开头
+        let (negated, start) = match words.first() {
+            Some((word, span)) if word.eq_ignore_ascii_case(NO_KEYWORD) && span.end <= end => {
+                (true, span.end)
+            }
+            _ => (false, 0),
+        };
+        // 输出模式：其余部分
+        let matcher = OutputMatcher::parse(&tail[start..end]).map_err(|e| e.offset(start))?;
+        Ok(Self {
+            negated,
+            matcher,
+            within,
+        })
+    }
+
+    fn format_tail(&self) -> String {
+        let mut words = vec![];
+        if self.negated {
+            words.push(NO_KEYWORD.to_owned());
+        }
+        words.push(self.matcher.to_string());
+        if let Some(within) = self.within {
+            words.push(format!("{WITHIN_KEYWORD} {within}"));
+        }
+        words.join(" ")
+    }
+}
+
+impl Display for Expectation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{EXPECT_HEAD} {}", self.format_tail())
+    }
+}
+
+/// 测试脚本中的一步
+#[derive(Debug, Clone, PartialEq)]
+pub enum TestStep {
+    /// 输入指令
+    Cmd(Cmd),
+    /// 检查输出预期
+    Expect(Expectation),
+}
+
+/// 测试脚本中的一行
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestLine {
+    /// 所在行号（从1开始）
+    pub line_no: usize,
+    /// 该行的内容
+    pub step: TestStep,
+}
+
+/// NAVM测试脚本
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TestScript {
+    /// 各行（按出现顺序）
+    pub lines: Vec<TestLine>,
+}
+
+impl TestScript {
+    /// 解析测试脚本
+    /// * 🚩所有解析错误均被收集
+    pub fn parse(src: &str) -> Result<Self, Vec<ScriptError>> {
+        Self::parse_with_options(src, &ParseOptions::default())
+    }
+
+    /// 按解析选项解析测试脚本
+    /// * 🚩在选项的注册表中追加[`Expectation`]
+    pub fn parse_with_options(src: &str, options: &ParseOptions) -> Result<Self, Vec<ScriptError>> {
+        let options = Self::parse_options(options.clone());
+        CmdScript::parse_with_options(src, &options)
+            .into_result()
+            .map(Self::from_cmds)
+    }
+
+    /// 在解析选项的注册表中追加[`Expectation`]
+    /// * 🎯供[预处理器](crate::cmd::Preprocessor)等其它解析入口使用
+    pub fn parse_options(mut options: ParseOptions) -> ParseOptions {
+        options.registry.register::<Expectation>();
+        options
+    }
+
+    /// 从已解析的指令构造
+    /// * 🚩其中的[`Expectation`]扩展指令⇒输出预期；其余⇒输入指令
+    pub fn from_cmds(cmds: impl IntoIterator<Item = ScriptCmd>) -> Self {
+        let lines = cmds
+            .into_iter()
+            .map(|ScriptCmd { line_no, cmd }| {
+                let expectation = match &cmd {
+                    Cmd::Ext(ext) => ext.downcast_ref::<Expectation>().cloned(),
+                    _ => None,
+                };
+                let step = match expectation {
+                    Some(expectation) => TestStep::Expect(expectation),
+                    None => TestStep::Cmd(cmd),
+                };
+                TestLine { line_no, step }
+            })
+            .collect();
+        Self { lines }
+    }
+
+    /// 仅含[`Expectation`]的注册表
+    pub fn registry() -> CmdRegistry {
+        Self::parse_options(ParseOptions::default()).registry
+    }
+}
+
+/// 单个输出预期的检查结果
+#[derive(Debug, Clone)]
+pub struct ExpectationResult {
+    /// 所在行号
+    pub line_no: usize,
+    /// 输出预期
+    pub expectation: Expectation,
+    /// 是否通过
+    pub passed: bool,
+    /// 与预期相符的输出
+    /// * 📌「预期出现」通过 / 「预期不出现」未通过 时存在
+    pub matched: Option<Output>,
+    /// 为此额外推理的周期数
+    pub cycles: usize,
+    /// 检查期间收集到的输出
+    /// * 📌「预期出现」：自上一个预期以来的输出
+    /// * 📌「预期不出现」：自此行起、检查范围内的输出
+    pub outputs: Vec<Output>,
+}
+
+impl Display for ExpectationResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mark = match self.passed {
+            true => "✅",
+            false => "❌",
+        };
+        write!(f, "{mark} line {}: {}", self.line_no, self.expectation)?;
+        if let Some(output) = &self.matched {
+            write!(f, " <= [{}] {}", output.type_name(), output.raw_content())?;
+        }
+        Ok(())
+    }
+}
+
+/// 测试报告
+#[derive(Debug, Clone, Default)]
+pub struct TestReport {
+    /// 各输出预期的检查结果（按行号排序）
+    pub results: Vec<ExpectationResult>,
+    /// 运行期间收集到的所有输出
+    pub outputs: Vec<Output>,
+}
+
+impl TestReport {
+    /// 是否全部通过
+    pub fn passed(&self) -> bool {
+        self.results.iter().all(|result| result.passed)
+    }
+
+    /// 未通过的检查结果
+    pub fn failures(&self) -> impl Iterator<Item = &ExpectationResult> {
+        self.results.iter().filter(|result| !result.passed)
+    }
+}
+
+impl Display for TestReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for result in &self.results {
+            writeln!(f, "{result}")?;
+        }
+        let passed = self.results.iter().filter(|result| result.passed).count();
+        write!(f, "{passed}/{} passed", self.results.len())
+    }
+}
+
+/// 测试脚本运行器
+/// * 🚩逐行输入指令、收集输出；遇到输出预期时，按需输入[`Cmd::CYC`]推进推理
+/// * 📌CIN终止后不再推进，尚未满足的预期均不通过
+#[derive(Debug, Clone)]
+pub struct TestRunner {
+    /// 每次推进的周期数
+    cycle_step: usize,
+    /// 每次输入后、收集输出前的等待时间
+    /// * 🎯异步运作的CIN（如子进程）需要时间产生输出
+    settle_time: Duration,
+}
+
+impl Default for TestRunner {
+    fn default() -> Self {
+        Self {
+            cycle_step: 1,
+            settle_time: Duration::ZERO,
+        }
+    }
+}
+
+/// 运行过程中的状态
+struct RunState {
+    /// 收集到的输出
+    outputs: Vec<Output>,
+    /// CIN是否已终止
+    terminated: bool,
+}
+
+impl TestRunner {
+    /// 构造函数
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置每次推进的周期数
+    /// * 🚩至少为1
+    pub fn with_cycle_step(mut self, cycle_step: usize) -> Self {
+        self.cycle_step = cycle_step.max(1);
+        self
+    }
+
+    /// 设置每次输入后的等待时间
+    pub fn with_settle_time(mut self, settle_time: Duration) -> Self {
+        self.settle_time = settle_time;
+        self
+    }
+
+    /// 输入指令，并收集此后的输出
+    fn input(
+        &self,
+        vm: &mut (impl VmRuntime + ?Sized),
+        state: &mut RunState,
+        cmd: Cmd,
+    ) -> Result<()> {
+        if state.terminated {
+            return Ok(());
+        }
+        vm.input_cmd(cmd)?;
+        if !self.settle_time.is_zero() {
+            std::thread::sleep(self.settle_time);
+        }
+        for output in vm.drain_outputs() {
+            let output = output?;
+            state.terminated |= output.is_type(TERMINATED);
+            state.outputs.push(output);
+        }
+        state.terminated |= vm.is_terminated();
+        Ok(())
+    }
+
+    /// 推理至多`max_cycles`个周期，直到`until`返回`true`
+    /// * 🚩返回实际推理的周期数
+    fn cycle_until(
+        &self,
+        vm: &mut (impl VmRuntime + ?Sized),
+        state: &mut RunState,
+        max_cycles: usize,
+        mut until: impl FnMut(&[Output]) -> bool,
+    ) -> Result<usize> {
+        let mut cycles = 0;
+        while cycles < max_cycles && !state.terminated && !until(&state.outputs) {
+            let step = self.cycle_step.min(max_cycles - cycles);
+            self.input(vm, state, Cmd::CYC(step))?;
+            cycles += step;
+        }
+        Ok(cycles)
+    }
+
+    /// 在虚拟机上运行测试脚本
+    /// * 🚩输入指令出错⇒中止运行并返回错误
+    pub fn run(
+        &self,
+        vm: &mut (impl VmRuntime + ?Sized),
+        script: &TestScript,
+    ) -> Result<TestReport> {
+        let mut state = RunState {
+            outputs: vec![],
+            terminated: vm.is_terminated(),
+        };
+        let mut results = vec![];
+        // 尚待检查的「预期不出现」：（行号，预期，起始输出索引）
+        let mut deferred = vec![];
+        // 「预期出现」的上下文起点
+        let mut context_start = 0;
+        for TestLine { line_no, step } in &script.lines {
+            let expectation = match step {
+                TestStep::Cmd(cmd) => {
+                    self.input(vm, &mut state, cmd.clone())?;
+                    continue;
+                }
+                TestStep::Expect(expectation) => expectation,
+            };
+            let start = state.outputs.len();
+            let result = match (expectation.negated, expectation.within) {
+                // 预期出现：在上下文起点之后的输出中查找，不足则推进
+                // * 🚩已被此前的预期「消费」的输出不再参与匹配
+                (false, within) => {
+                    let find = |outputs: &[Output]| {
+                        outputs[context_start..]
+                            .iter()
+                            .find(|output| expectation.matches(output))
+                            .cloned()
+                    };
+                    let cycles = self.cycle_until(
+                        vm,
+                        &mut state,
+                        within.unwrap_or(0),
+                        |outputs| find(outputs).is_some(),
+                    )?;
+                    let matched = find(&state.outputs);
+                    let outputs = state.outputs[context_start..].to_vec();
+                    context_start = state.outputs.len();
+                    ExpectationResult {
+                        line_no: *line_no,
+                        expectation: expectation.clone(),
+                        passed: matched.is_some(),
+                        matched,
+                        cycles,
+                        outputs,
+                    }
+                }
+                // 预期不出现 & 有周期数：推进完毕后检查
+                (true, Some(within)) => {
+                    let cycles = self.cycle_until(vm, &mut state, within, |_| false)?;
+                    let outputs = state.outputs[start..].to_vec();
+                    Self::check_absent(*line_no, expectation, cycles, outputs)
+                }
+                // 预期不出现 & 无周期数：推迟到脚本结束
+                (true, None) => {
+                    deferred.push((*line_no, expectation, start));
+                    continue;
+                }
+            };
+            results.push(result);
+        }
+        for (line_no, expectation, start) in deferred {
+            let outputs = state.outputs[start..].to_vec();
+            results.push(Self::check_absent(line_no, expectation, 0, outputs));
+        }
+        results.sort_by_key(|result| result.line_no);
+        Ok(TestReport {
+            results,
+            outputs: state.outputs,
+        })
+    }
+
+    /// 检查「预期不出现」
+    fn check_absent(
+        line_no: usize,
+        expectation: &Expectation,
+        cycles: usize,
+        outputs: Vec<Output>,
+    ) -> ExpectationResult {
+        let matched = outputs
+            .iter()
+            .find(|output| expectation.matches(output))
+            .cloned();
+        ExpectationResult {
+            line_no,
+            expectation: expectation.clone(),
+            passed: matched.is_none(),
+            matched,
+            cycles,
+            outputs,
+        }
+    }
+}
+
+/// 单元测试
+/// * 🚩测试用虚拟机亦供其它模块的测试使用
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::{output::Operation, vm::VmStatus};
+    use narsese::conversion::string::impl_lexical::format_instances::FORMAT_ASCII;
+    use nar_dev_utils::asserts;
+    use std::collections::VecDeque;
+
+    /// 测试用虚拟机
+    /// * 🚩在预定的周期产生预定的输出
+    pub(crate) struct MockVm {
+        /// 当前周期
+        cycle: usize,
+        /// 预定的输出：（周期，输出）
+        scheduled: VecDeque<(usize, Output)>,
+        /// 输出缓存
+        cache: VecDeque<Output>,
+        /// 状态
+        status: VmStatus,
+    }
+
+    impl MockVm {
+        pub(crate) fn new(scheduled: impl IntoIterator<Item = (usize, Output)>) -> Self {
+            Self {
+                cycle: 0,
+                scheduled: scheduled.into_iter().collect(),
+                cache: VecDeque::new(),
+                status: VmStatus::Running,
+            }
+        }
+    }
+
+    impl VmRuntime for MockVm {
+        fn input_cmd(&mut self, cmd: Cmd) -> Result<()> {
+            match cmd {
+                Cmd::CYC(steps) => self.cycle += steps,
+                cmd => self.cache.push_back(Output::IN {
+                    content: cmd.to_string(),
+                    narsese: None,
+                }),
+            }
+            while let Some((cycle, _)) = self.scheduled.front() {
+                if *cycle > self.cycle {
+                    break;
+                }
+                let (_, output) = self.scheduled.pop_front().unwrap();
+                self.cache.push_back(output);
+            }
+            Ok(())
+        }
+
+        fn fetch_output(&mut self) -> Result<Output> {
+            self.cache
+                .pop_front()
+                .ok_or_else(|| anyhow::anyhow!("缓存已空"))
+        }
+
+        fn try_fetch_output(&mut self) -> Result<Option<Output>> {
+            Ok(self.cache.pop_front())
+        }
+
+        fn status(&self) -> &VmStatus {
+            &self.status
+        }
+
+        fn terminate(&mut self) -> Result<()> {
+            self.status = VmStatus::Terminated(Ok(()));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let script = TestScript::parse(
+            "NSE <A --> B>.
+            expect answer <A --> C>. WITHIN 100 // 注释
+            EXPECT EXE ^left
+            EXPECT NO ERROR",
+        )
+        .unwrap();
+        let expectations = script
+            .lines
+            .iter()
+            .filter_map(|line| match &line.step {
+                TestStep::Expect(expectation) => Some(expectation.to_string()),
+                TestStep::Cmd(..) => None,
+            })
+            .collect::<Vec<_>>();
+        asserts! {
+            script.lines.len() => 4
+            script.lines[0].step => TestStep::Cmd(Cmd::parse("NSE <A --> B>.").unwrap())
+            expectations => [
+                "EXPECT ANSWER <A --> C>. WITHIN 100",
+                "EXPECT EXE ^left",
+                "EXPECT NO ERROR",
+            ]
+        }
+        // 错误：所有错误均被收集，且范围位于整行中
+        let errors = TestScript::parse("EXPECT\nEXPECT OUT <A --> B WITHIN 1\nEXPECT OUT WITHIN x")
+            .unwrap_err();
+        let spans = errors.iter().map(|e| e.error.span()).collect::<Vec<_>>();
+        asserts! {
+            errors.len() => 3
+            spans[1] => 11..19
+            spans[2] => 18..19
+        }
+    }
+
+    #[test]
+    fn test_run() {
+        let answer = FORMAT_ASCII.parse("<A --> C>. %1.0;0.81%").unwrap();
+        let mut vm = MockVm::new([
+            (
+                5,
+                Output::ANSWER {
+                    content_raw: "<A --> C>. %1.0;0.81%".into(),
+                    narsese: Some(answer),
+                },
+            ),
+            (
+                7,
+                Output::EXE {
+                    content_raw: "^left()".into(),
+                    operation: Operation::new("left", []),
+                },
+            ),
+            (
+                15,
+                Output::ERROR {
+                    description: "oops".into(),
+                },
+            ),
+        ]);
+        let script = TestScript::parse(
+            "NSE <A --> B>.
+            NSE <B --> C>.
+            NSE <A --> C>?
+            EXPECT ANSWER <?x --> C> c>=0.5 WITHIN 10
+            EXPECT EXE ^left WITHIN 3
+            EXPECT NO ERROR
+            EXPECT OUT <A --> D> WITHIN 3
+            CYC 10",
+        )
+        .unwrap();
+        let report = TestRunner::new().run(&mut vm, &script).unwrap();
+        println!("{report}");
+        let summary = report
+            .results
+            .iter()
+            .map(|result| (result.line_no, result.passed, result.cycles))
+            .collect::<Vec<_>>();
+        asserts! {
+            report.passed() => false
+            summary => [(4, true, 5), (5, true, 2), (6, false, 0), (7, false, 3)]
+            vm.cycle => 20
+            report.failures().count() => 2
+            report.results[2].matched.as_ref().unwrap().is_type("ERROR") => true
+            // 上下文：三条输入回显+回答
+            report.results[0].outputs.len() => 4
+        }
+    }
+
+    #[test]
+    fn test_run_stale_output() {
+        let mut vm = MockVm::new([(
+            2,
+            Output::ANSWER {
+                content_raw: "<A --> C>.".into(),
+                narsese: None,
+            },
+        )]);
+        // 第二个预期不能由第一个预期已匹配过的输出满足
+        let script = TestScript::parse(
+            "EXPECT ANSWER WITHIN 5
+            EXPECT ANSWER WITHIN 3",
+        )
+        .unwrap();
+        let report = TestRunner::new().run(&mut vm, &script).unwrap();
+        let summary = report
+            .results
+            .iter()
+            .map(|result| (result.line_no, result.passed, result.cycles))
+            .collect::<Vec<_>>();
+        asserts! {
+            summary => [(1, true, 2), (2, false, 3)]
+            report.results[1].matched.is_none() => true
+        }
+    }
+}
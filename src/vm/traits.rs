@@ -1,8 +1,12 @@
 //! 引入「非公理虚拟机」的特征
 
-use super::VmStatus;
+use super::{VmCapabilities, VmStatus};
 use crate::{cmd::Cmd, output::Output};
 use anyhow::Result;
+use std::time::{Duration, Instant};
+
+/// [`VmRuntime::fetch_output_timeout`]默认实现的轮询间隔
+pub const FETCH_POLL_INTERVAL: Duration = Duration::from_millis(1);
 
 /// 虚拟机运行时
 /// * 🎯所有**已启动**的「非公理虚拟机」遵循的特征
@@ -43,6 +47,26 @@ pub trait VmRuntime {
     /// * ❌【2024-03-24 23:29:16】可能无法对一些类型实现`has_output`，故不提供默认实现
     fn try_fetch_output(&mut self) -> Result<Option<Output>>;
 
+    /// 从虚拟机中获取一个输出，至多等待指定的时长
+    /// * 🎯「最多等500ms以获取下一个输出」
+    /// * 🚩超时仍无输出⇒[`None`]
+    /// * 🚩默认实现：以[`FETCH_POLL_INTERVAL`]为间隔，轮询[`VmRuntime::try_fetch_output`]
+    ///   * 📌虚拟机已终止且无输出⇒不再等待，直接返回[`None`]
+    ///   * 💡基于通道等机制的运行时可覆盖此方法，以免轮询
+    fn fetch_output_timeout(&mut self, timeout: Duration) -> Result<Option<Output>> {
+        let start = Instant::now();
+        loop {
+            if let Some(output) = self.try_fetch_output()? {
+                return Ok(Some(output));
+            }
+            let elapsed = start.elapsed();
+            if elapsed >= timeout || self.is_terminated() {
+                return Ok(None);
+            }
+            std::thread::sleep(FETCH_POLL_INTERVAL.min(timeout - elapsed));
+        }
+    }
+
     // 生命周期 //
 
     ///【抽象】获取虚拟机状态
@@ -63,6 +87,15 @@ pub trait VmRuntime {
     /// * 🚩【2024-04-02 20:33:51】目前从所有权变回可变引用，销毁的逻辑交给外部调用者
     ///   * 📄BabelNAR中多线程交互的情况：难以从`Arc<Mutex>`中拿取所有权
     fn terminate(&mut self) -> Result<()>;
+
+    // 能力 //
+
+    /// 获取虚拟机的能力描述
+    /// * 🎯让调用者在输入前用[`Cmd::validate`]检查指令
+    /// * 🚩默认「无限制」
+    fn capabilities(&self) -> VmCapabilities {
+        VmCapabilities::default()
+    }
 }
 
 // ! ❌【2024-03-24 23:28:30】无法自动实现`Drop`特征：孤儿规则「不该触及其它地方实现的类型」
@@ -95,4 +128,41 @@ pub trait VmLauncher {
     ///   * 📄如「命令行运行时」会立即启动子进程（及其辅助线程）
     /// * 🚩【2024-04-02 04:13:25】因「反馈并处理错误」的需要，现在需要[`Result`]
     fn launch(self) -> Result<Self::Runtime>;
+
+    /// 获取将启动的虚拟机的能力描述
+    /// * 🎯在启动前即可检查指令
+    /// * 🚩默认「无限制」
+    fn capabilities(&self) -> VmCapabilities {
+        VmCapabilities::default()
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vm::test_script::tests::MockVm;
+
+    #[test]
+    fn test_fetch_output_timeout() {
+        let output = Output::INFO {
+            message: "info".into(),
+        };
+        let mut vm = MockVm::new([(1, output.clone())]);
+        let timeout = Duration::from_millis(20);
+        // 无输出⇒等待至超时
+        let start = Instant::now();
+        assert_eq!(vm.fetch_output_timeout(timeout).unwrap(), None);
+        assert!(start.elapsed() >= timeout);
+        // 有输出⇒立即返回
+        vm.input_cmd(Cmd::CYC(1)).unwrap();
+        let start = Instant::now();
+        assert_eq!(vm.fetch_output_timeout(timeout).unwrap(), Some(output));
+        assert!(start.elapsed() < timeout);
+        // 已终止⇒不再等待
+        vm.terminate().unwrap();
+        let start = Instant::now();
+        assert_eq!(vm.fetch_output_timeout(Duration::from_secs(10)).unwrap(), None);
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
 }